@@ -0,0 +1,232 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+
+use ::toml;
+
+use super::err::{CompositerError, Result};
+use super::{SPEC_LIB_EXT, SPEC_LIB_EXT_LEGACY};
+
+/// The name of build section.
+const SPEC_BUILD: &'static str = "build";
+/// The name of backend label.
+const SPEC_BUILD_BACKEND: &'static str = "backend";
+/// The name of command label.
+const SPEC_BUILD_COMMAND: &'static str = "command";
+/// The name of artifact label.
+const SPEC_BUILD_ARTIFACT: &'static str = "artifact";
+/// The output sub-directory of cargo.
+const SPEC_BUILD_CARGO_OUTPUT: &'static str = "target/release";
+/// The output sub-directory of cmake.
+const SPEC_BUILD_CMAKE_OUTPUT: &'static str = "build";
+
+/// The enum `Backend` is the runner of a library's build.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Backend {
+    /// `make` from the repository.
+    Make,
+    /// `cargo build --release` from the repository.
+    Cargo,
+    /// `cmake` to the `build` sub-directory.
+    Cmake,
+    /// A arbitrary shell command from the repository.
+    Command(String),
+}
+
+impl Backend {
+    /// The accessor method `get_commands` returns the processes
+    /// to run from the repository.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    pub fn get_commands(&self, source: &Path) -> Vec<process::Command> {
+        match *self {
+            Backend::Make => vec![process::Command::new("make")],
+            Backend::Cargo => {
+                let mut cargo = process::Command::new("cargo");
+
+                cargo.arg("build").arg("--release");
+                vec![cargo]
+            },
+            Backend::Cmake => {
+                let mut configure = process::Command::new("cmake");
+                let mut build = process::Command::new("cmake");
+
+                configure.arg("-S").arg(".")
+                         .arg("-B").arg(SPEC_BUILD_CMAKE_OUTPUT);
+                build.arg("--build").arg(SPEC_BUILD_CMAKE_OUTPUT);
+                vec![configure, build]
+            },
+            Backend::Command(ref line) => {
+                let mut shell = process::Command::new("sh");
+
+                shell.arg("-c").arg(line);
+                vec![shell]
+            },
+        }.into_iter()
+         .map(|mut command| {
+             command.current_dir(source);
+             command
+         })
+         .collect::<Vec<process::Command>>()
+    }
+
+    /// The accessor method `get_output` returns the directory
+    /// where the backend produces the dynamic library.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    pub fn get_output(&self, source: &Path) -> PathBuf {
+        match *self {
+            Backend::Cargo => source.join(SPEC_BUILD_CARGO_OUTPUT),
+            Backend::Cmake => source.join(SPEC_BUILD_CMAKE_OUTPUT),
+            Backend::Make | Backend::Command(_) => source.to_path_buf(),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses the backend's name
+    /// like `make`, `cargo` or `cmake`.
+    fn from_str(backend: &str) -> Result<Self> {
+        match backend {
+            "make" => Ok(Backend::Make),
+            "cargo" => Ok(Backend::Cargo),
+            "cmake" => Ok(Backend::Cmake),
+            _ => Err(CompositerError::BuildBackend(backend.to_string())),
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Backend {
+        Backend::Make
+    }
+}
+
+/// The struct `Build` is the `[build]` section of a manifest.
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Build {
+    /// The runner of the build.
+    backend: Backend,
+    /// The declared path of the dynamic library from the repository.
+    artifact: Option<PathBuf>,
+}
+
+impl Build {
+    /// The constructor method `from_manifest` returns the build section
+    /// of a manifest, a arbitrary `command` replaces the `backend` and
+    /// the `make` backend is the default. The `artifact` is a relative
+    /// path which stays inside the repository.
+    pub fn from_manifest(manifest: &toml::Table) -> Result<Self> {
+        match manifest.get(SPEC_BUILD) {
+            None => Ok(Build::default()),
+            Some(value) => {
+                let build: &toml::Table = try!(value.as_table().ok_or_else(||
+                    CompositerError::ManifestKey(SPEC_BUILD.to_string(), value.to_string())));
+                let artifact: Option<PathBuf> = match try!(get_str(build, SPEC_BUILD_ARTIFACT)) {
+                    None => None,
+                    Some(artifact) => Some(try!(get_artifact(artifact))),
+                };
+
+                match (try!(get_str(build, SPEC_BUILD_COMMAND)),
+                       try!(get_str(build, SPEC_BUILD_BACKEND))) {
+                    (Some(command), _) => Ok(Backend::Command(command.to_string())),
+                    (None, Some(backend)) => backend.parse::<Backend>(),
+                    (None, None) => Ok(Backend::default()),
+                }.and_then(|backend| Ok(Build {
+                    backend: backend,
+                    artifact: artifact,
+                }))
+            },
+        }
+    }
+
+    pub fn get_backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    pub fn get_artifact(&self) -> Option<&PathBuf> {
+        self.artifact.as_ref()
+    }
+
+    /// The method `find_artifact` returns the path of the produced
+    /// dynamic library: the declared artifact if it resolves inside
+    /// the repository, else `<name>.<ext>` or
+    /// `lib<name>.<ext>` from the backend's output with the platform's
+    /// or the legacy `dylib` extention, else the single dynamic library
+    /// of this output.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    /// @ name: `libnya`.
+    pub fn find_artifact(&self, source: &Path, name: &str) -> Option<PathBuf> {
+        if let Some(ref artifact) = self.artifact {
+            match (source.canonicalize(), source.join(artifact).canonicalize()) {
+                (Ok(ref source), Ok(artifact)) if artifact.starts_with(source) &&
+                                                  artifact.is_file() => Some(artifact),
+                _ => None,
+            }
+        } else {
+            let output: PathBuf = self.backend.get_output(source);
+
+            [SPEC_LIB_EXT, SPEC_LIB_EXT_LEGACY].iter()
+                  .flat_map(|extension| vec![
+                      output.join(name).with_extension(extension),
+                      output.join(format!("lib{}", name)).with_extension(extension),
+                  ])
+                  .find(|path| path.is_file())
+                  .or_else(|| {
+                      fs::read_dir(&output).ok().and_then(|entries| {
+                          let libraries: Vec<PathBuf> =
+                              entries.filter_map(|entry| entry.ok())
+                                     .map(|entry| entry.path())
+                                     .filter(|path| path.is_file() &&
+                                             path.extension()
+                                                 .and_then(|extension| extension.to_str())
+                                                 .map_or(false, |extension|
+                                                     extension.eq(SPEC_LIB_EXT) ||
+                                                     extension.eq(SPEC_LIB_EXT_LEGACY)))
+                                     .collect::<Vec<PathBuf>>();
+                          if libraries.len() == 1 {
+                              libraries.into_iter().next()
+                          } else {
+                              None
+                          }
+                      })
+                  })
+        }
+    }
+}
+
+/// The function `get_str` returns the string of a optional key
+/// of the build section.
+/// @ key: `command`.
+fn get_str<'a>(build: &'a toml::Table, key: &str) -> Result<Option<&'a str>> {
+    match build.get(key) {
+        None => Ok(None),
+        Some(value) => {
+            value.as_str()
+                 .map(Some)
+                 .ok_or_else(|| CompositerError::ManifestKey(
+                     format!("{}.{}", SPEC_BUILD, key), value.to_string()
+                 ))
+        },
+    }
+}
+
+/// The function `get_artifact` returns the declared path of the dynamic
+/// library, a absolute path or a parent directory is refused because
+/// the artifact can't leave the repository.
+/// @ artifact: `out/libnya.so`.
+fn get_artifact(artifact: &str) -> Result<PathBuf> {
+    let path: PathBuf = PathBuf::from(artifact);
+
+    if path.components().all(|component| match component {
+        Component::Normal(_) | Component::CurDir => true,
+        _ => false,
+    }) {
+        Ok(path)
+    } else {
+        Err(CompositerError::ManifestKey(
+            format!("{}.{}", SPEC_BUILD, SPEC_BUILD_ARTIFACT), artifact.to_string()
+        ))
+    }
+}
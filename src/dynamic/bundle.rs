@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::io;
+use std::iter;
+use std::ops::Not;
+use std::path::Path;
+
+use ::tar;
+
+use super::err::{CompositerError, Result};
+use super::lock::Lock;
+use super::{SPEC_LOCK, SPEC_SUBD_GIT};
+
+/// The function `pack` writes a archive with the repositories
+/// of the pinned libraries and the Neko.lock which pins them.
+/// @ dest: `$HOME/.neko/arukana@libnya.tar`.
+/// @ git: `$HOME/.neko/git`.
+pub fn pack(dest: &Path, git: &Path, lock: &Lock) -> Result<()> {
+    let buffer: String = lock.to_string();
+    let mut header: tar::Header = tar::Header::new_gnu();
+
+    header.set_size(buffer.len() as u64);
+    header.set_mode(0o644);
+    File::create(dest).and_then(|descripter| {
+        let mut builder: tar::Builder<File> = tar::Builder::new(descripter);
+
+        for (libraryname, _) in lock {
+            try!(builder.append_dir_all(Path::new(SPEC_SUBD_GIT).join(libraryname),
+                                        git.join(libraryname)));
+        }
+        try!(builder.append_data(&mut header, SPEC_LOCK, buffer.as_bytes()));
+        builder.into_inner().and(Ok(()))
+    }).or_else(|why: io::Error| Err(CompositerError::BundleWrite(why)))
+}
+
+/// The function `unpack` extracts a archive and returns
+/// the Neko.lock of its libraries, unless a pinned library or
+/// one of its dependencies isn't named like `account@repo`.
+/// @ path: `arukana@libnya.tar`.
+/// @ dest: `$HOME/.neko/bundle`.
+pub fn unpack(path: &Path, dest: &Path) -> Result<Lock> {
+    let lock: Lock = try!(File::open(path).and_then(|descripter|
+        tar::Archive::new(descripter).unpack(dest)
+    ).or_else(|why: io::Error| Err(CompositerError::BundleRead(why)))
+     .and_then(|_| Lock::from_path(&dest.join(SPEC_LOCK))));
+
+    for (libraryname, locked) in &lock {
+        if let Some(libraryname) = iter::once(libraryname).chain(locked.get_dependencies())
+                                                         .find(|name| is_libraryname(name).not()) {
+            return Err(CompositerError::BundleName(libraryname.clone()));
+        }
+    }
+    Ok(lock)
+}
+
+/// The function `is_libraryname` returns true for a name like
+/// `arukana@libnya` or `local@libnya`, which stays a single
+/// entry of the git sub-directory.
+fn is_libraryname(libraryname: &str) -> bool {
+    let parts: Vec<&str> = libraryname.split('@').collect();
+
+    parts.len() == 2 &&
+    parts.iter().all(|part| part.is_empty().not() &&
+                            part.ne(&".") && part.ne(&"..") &&
+                            part.contains(|c: char| c.eq(&'/') || c.eq(&'\\') || c.eq(&'\0')).not())
+}
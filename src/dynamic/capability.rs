@@ -0,0 +1,163 @@
+use std::fmt;
+use std::ops::Not;
+use std::str::FromStr;
+
+use ::toml;
+
+use super::err::{CompositerError, Result};
+
+/// The separator of a list of capabilities like `input,output`.
+const SPEC_CAPABILITY_SEPARATOR: char = ',';
+
+/// The enum `Capability` is a right which a library must be granted
+/// to receive a event or to change the state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+    /// Receives the keystrokes, the mouse and the pasted text.
+    Input = 0b00001,
+    /// Receives the terminal's output.
+    Output = 0b00010,
+    /// Receives the name of the running process.
+    Process = 0b00100,
+    /// Locks the input from the terminal.
+    Lock = 0b01000,
+    /// Changes the persona and its tooltip.
+    Persona = 0b10000,
+}
+
+/// The list of every capability.
+pub const SPEC_CAPABILITIES: [Capability; 5] = [
+    Capability::Input, Capability::Output, Capability::Process,
+    Capability::Lock, Capability::Persona,
+];
+
+impl Capability {
+    /// The accessor method `get_name` returns the Neko.toml's name
+    /// of the capability.
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            Capability::Input => "input",
+            Capability::Output => "output",
+            Capability::Process => "process",
+            Capability::Lock => "lock",
+            Capability::Persona => "persona",
+        }
+    }
+}
+
+impl FromStr for Capability {
+    type Err = CompositerError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        SPEC_CAPABILITIES.iter()
+                         .find(|capability| capability.get_name().eq(name))
+                         .cloned()
+                         .ok_or_else(|| CompositerError::ParseCapability(name.to_string()))
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+/// The struct `Capabilities` is a set of capabilities requested
+/// by a manifest or granted by the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    bits: u8,
+}
+
+impl Capabilities {
+    /// The constructor method `all` returns every capability.
+    pub fn all() -> Self {
+        SPEC_CAPABILITIES.iter().cloned().collect::<Capabilities>()
+    }
+
+    /// The constructor method `from_slice` returns the capabilities
+    /// of a toml's array like `["input", "persona"]`.
+    pub fn from_slice(slice: &[toml::Value]) -> Result<Self> {
+        slice.iter()
+             .map(|value| value.as_str()
+                               .ok_or_else(|| CompositerError::ParseCapability(value.to_string()))
+                               .and_then(|name| name.parse::<Capability>()))
+             .collect::<Result<Capabilities>>()
+    }
+
+    /// The method `to_slice` returns the toml's array of the capabilities.
+    pub fn to_slice(&self) -> Vec<toml::Value> {
+        self.iter()
+            .map(|capability| toml::Value::String(capability.get_name().to_string()))
+            .collect::<Vec<toml::Value>>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.eq(&0)
+    }
+
+    /// The method `contains` returns true if the capability is in the set.
+    pub fn contains(&self, capability: Capability) -> bool {
+        (self.bits & capability as u8).ne(&0)
+    }
+
+    /// The method `insert` adds a capability to the set.
+    pub fn insert(&mut self, capability: Capability) {
+        self.bits |= capability as u8;
+    }
+
+    /// The method `intersection` returns the capabilities of both sets,
+    /// like the requested capabilities which are granted.
+    pub fn intersection(&self, other: &Capabilities) -> Capabilities {
+        Capabilities { bits: self.bits & other.bits }
+    }
+
+    /// The method `difference` returns the capabilities which
+    /// aren't in the other set, like the requested capabilities
+    /// which aren't granted.
+    pub fn difference(&self, other: &Capabilities) -> Capabilities {
+        Capabilities { bits: self.bits & other.bits.not() }
+    }
+
+    /// The method `iter` returns the capabilities of the set.
+    pub fn iter(&self) -> ::std::vec::IntoIter<Capability> {
+        SPEC_CAPABILITIES.iter()
+                         .filter(|capability| self.contains(**capability))
+                         .cloned()
+                         .collect::<Vec<Capability>>()
+                         .into_iter()
+    }
+}
+
+impl ::std::iter::FromIterator<Capability> for Capabilities {
+    fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+        let mut capabilities: Capabilities = Capabilities::default();
+
+        for capability in iter {
+            capabilities.insert(capability);
+        }
+        capabilities
+    }
+}
+
+impl FromStr for Capabilities {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses a list like `input,output`.
+    fn from_str(buffer: &str) -> Result<Self> {
+        buffer.split(SPEC_CAPABILITY_SEPARATOR)
+              .map(|name| name.trim())
+              .filter(|name| name.is_empty().not())
+              .map(|name| name.parse::<Capability>())
+              .collect::<Result<Capabilities>>()
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.iter()
+                            .map(|capability| capability.get_name())
+                            .collect::<Vec<&str>>()
+                            .join(&SPEC_CAPABILITY_SEPARATOR.to_string()))
+    }
+}
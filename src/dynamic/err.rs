@@ -24,6 +24,18 @@ pub enum CompositerError {
     MkDirGit(io::Error),
     /// Can't create the `lib` sub-directory.
     MkDirLib(io::Error),
+    /// Can't create the `rollback` sub-directory.
+    MkDirRollback(io::Error),
+    /// Can't create the `log` sub-directory.
+    MkDirLog(io::Error),
+    /// Can't create the `include` sub-directory.
+    MkDirInclude(io::Error),
+    /// Can't write the neko.h header.
+    WriteHeader(io::Error),
+    /// Can't write the build log.
+    BuildLog(io::Error),
+    /// Can't swap the library with its previous version.
+    RollbackSwap(io::Error),
     /// Can't read the `git` sub-directory.
     ReadDirGit(io::Error),
     /// Can't read the `lib` sub-directory.
@@ -32,10 +44,36 @@ pub enum CompositerError {
     OpenDirLib(io::Error),
     /// Can't run the command.
     BuildCommand(io::Error),
+    /// Can't link the local directory.
+    InstallLocal(io::Error),
     /// Can't read the `manifest` Neko.toml file.
     ReadManifest(io::Error),
+    /// Can't read the `lock` Neko.lock file.
+    ReadLock(io::Error),
+    /// Can't write the `lock` Neko.lock file.
+    WriteLock(io::Error),
+    /// Can't read the registry's entry.
+    ReadRegistry(io::Error),
+    /// Can't write the bundle's archive.
+    BundleWrite(io::Error),
+    /// Can't unpack the bundle's archive.
+    BundleRead(io::Error),
+    /// Can't read the `mount` Neko.mount file.
+    ReadMount(io::Error),
+    /// Can't write the `mount` Neko.mount file.
+    WriteMount(io::Error),
+    /// Can't read the `trust` Neko.trust file.
+    ReadTrust(io::Error),
+    /// Can't write the `trust` Neko.trust file.
+    WriteTrust(io::Error),
+    /// Can't run gpg to verify the signature.
+    Signature(io::Error),
+    /// Can't read the dynamic library.
+    ReadLib(io::Error),
     /// Can't clone the repository.
     InstallClone(git2::Error),
+    /// Can't clone the registry.
+    RegistryClone(git2::Error),
     /// Can't update the repository.
     UpdateRepOpen(git2::Error),
     /// Can't found the origin from repository.
@@ -48,10 +86,52 @@ pub enum CompositerError {
     UpdateRepObject(git2::Error),
     /// Can't reset the repository.
     UpdateRepReset(git2::Error),
+    /// Can't found the revision from repository.
+    UpdateRepRevision(git2::Error),
+    /// Can't remember the revision from repository.
+    UpdateRepConfig(git2::Error),
+    /// Can't found the commit from repository's head.
+    LockRepHead(git2::Error),
+    /// Can't found the locked commit from repository.
+    LockRepCommit(git2::Error),
+    /// Can't compute the checksum of the dynamic library.
+    Checksum(git2::Error),
     /// Can't mount the dynamic library.
     Mount(LibraryError),
+    /// Can't reload the dynamic library.
+    Reload(LibraryError),
+    /// Can't load the updated dynamic library.
+    UpdateLoad(LibraryError),
+    /// Can't inspect the dynamic library.
+    Info(LibraryError),
     /// The build haven't exited with success.
     BuildExit(process::ExitStatus),
+    /// The build backend isn't supported.
+    BuildBackend(String),
+    /// Can't found the built dynamic library.
+    BuildArtifact,
+    /// The dependencies have a cycle like `[a, b, a]`.
+    DependencyCycle(Vec<String>),
+    /// No tagged version of a library satisfies every requirement
+    /// of its dependents.
+    VersionConflict(String, Vec<String>),
+    /// Can't parse a version or a version requirement.
+    ParseVersion(String),
+    /// A key of the manifest has a unvalid value.
+    ManifestKey(String, String),
+    /// The library requires a newer version of Neko.
+    ManifestNeko(String),
+    /// The capability isn't known.
+    ParseCapability(String),
+    /// The account to confirm, the repository and the commit
+    /// to build aren't trusted.
+    Untrusted(String, String, String),
+    /// The account to trust isn't named with its host.
+    TrustAccount(String),
+    /// The commit to build isn't signed.
+    Unsigned(String),
+    /// The signature of the commit to build isn't valid.
+    BadSignature(String),
     /// Can't get the target identifiant from branch.
     UpdateRepBranchId,
     /// Can't found the NEKO_PATH environement variable.
@@ -61,21 +141,150 @@ pub enum CompositerError {
     /// Can't remove the index.
     UnmountRemove,
     /// Can't parse the `manifest` Neko.toml file.
-    ParseManifest,
+    ParseManifest(String),
+    /// Can't parse the `lock` Neko.lock file.
+    ParseLock,
+    /// Can't parse the `mount` Neko.mount file.
+    ParseMount,
+    /// Can't parse the registry's entry.
+    ParseRegistry,
+    /// Can't parse the `trust` Neko.trust file.
+    ParseTrust,
+    /// Can't found the $NEKO_REGISTRY environement variable.
+    RegistryUnset,
+    /// Can't found the library from the registry.
+    RegistryName(String),
+    /// The library to bundle isn't pinned to the Neko.lock.
+    BundleLocked(String),
+    /// The library to bundle is built by cargo which needs the network.
+    BundleCargo(String),
+    /// The library of the bundle isn't named like `account@repo`.
+    BundleName(String),
+    /// The library to uninstall is required by installed libraries.
+    Dependents(String, Vec<String>),
+    /// Can't found a previous version of the library.
+    Rollback,
     /// Can't parse a integer from the table.
     ParseInteger,
     /// The lib git haven't a valid format.
     InstallFormat,
     /// The dynamic library as already a repository.
     InstallExists,
+    /// A installation already runs in background.
+    InstallBusy,
     Io(io::Error),
 }
 
 impl fmt::Display for CompositerError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CompositerError::DependencyCycle(ref cycle) => {
+                write!(f, "{} {}", self.description(), cycle.join(" -> "))
+            },
+            CompositerError::VersionConflict(ref libraryname, ref requirements) => {
+                write!(f, "{} {} ({})", self.description(), libraryname,
+                       requirements.join(", "))
+            },
+            CompositerError::BuildBackend(ref backend) => {
+                write!(f, "{} \"{}\"", self.description(), backend)
+            },
+            CompositerError::ParseVersion(ref version) => {
+                write!(f, "{} \"{}\"", self.description(), version)
+            },
+            CompositerError::ManifestKey(ref key, ref value) => {
+                write!(f, "{} {} = {}", self.description(), key, value)
+            },
+            CompositerError::ManifestNeko(ref version) => {
+                write!(f, "{} {}", self.description(), version)
+            },
+            CompositerError::ParseManifest(ref errors) => {
+                write!(f, "{} {}", self.description(), errors)
+            },
+            CompositerError::ParseCapability(ref name) => {
+                write!(f, "{} {}", self.description(), name)
+            },
+            CompositerError::Dependents(ref libraryname, ref dependents) => {
+                write!(f, "{} {} ({})", self.description(), libraryname,
+                       dependents.join(", "))
+            },
+            CompositerError::Untrusted(ref account, ref repository, ref commit) => {
+                write!(f, "The repository {} at {} isn't trusted, confirm with `neko trust {}`",
+                       repository, commit, account)
+            },
+            CompositerError::BundleCargo(ref libraryname) |
+            CompositerError::BundleName(ref libraryname) => {
+                write!(f, "{} {}", self.description(), libraryname)
+            },
+            CompositerError::TrustAccount(ref account) => {
+                write!(f, "{} {}", self.description(), account)
+            },
+            CompositerError::Unsigned(ref commit) |
+            CompositerError::BadSignature(ref commit) => {
+                write!(f, "{} {}", self.description(), commit)
+            },
+            CompositerError::RegistryName(ref libraryname) |
+            CompositerError::BundleLocked(ref libraryname) => {
+                write!(f, "{} {}", self.description(), libraryname)
+            },
+            CompositerError::MvFail(ref why) |
+            CompositerError::RmFile(ref why) |
+            CompositerError::RmDir(ref why) |
+            CompositerError::MkDirGit(ref why) |
+            CompositerError::MkDirLib(ref why) |
+            CompositerError::MkDirRollback(ref why) |
+            CompositerError::MkDirLog(ref why) |
+            CompositerError::MkDirInclude(ref why) |
+            CompositerError::WriteHeader(ref why) |
+            CompositerError::BuildLog(ref why) |
+            CompositerError::RollbackSwap(ref why) |
+            CompositerError::ReadDirGit(ref why) |
+            CompositerError::ReadDirLib(ref why) |
+            CompositerError::OpenDirLib(ref why) |
+            CompositerError::BuildCommand(ref why) |
+            CompositerError::InstallLocal(ref why) |
+            CompositerError::ReadManifest(ref why) |
+            CompositerError::ReadLock(ref why) |
+            CompositerError::WriteLock(ref why) |
+            CompositerError::ReadRegistry(ref why) |
+            CompositerError::BundleWrite(ref why) |
+            CompositerError::BundleRead(ref why) |
+            CompositerError::ReadMount(ref why) |
+            CompositerError::WriteMount(ref why) |
+            CompositerError::ReadTrust(ref why) |
+            CompositerError::WriteTrust(ref why) |
+            CompositerError::Signature(ref why) |
+            CompositerError::ReadLib(ref why) => {
+                write!(f, "{} {}", self.description(), why)
+            },
+            CompositerError::InstallClone(ref why) |
+            CompositerError::RegistryClone(ref why) |
+            CompositerError::UpdateRepOpen(ref why) |
+            CompositerError::UpdateRepOrigin(ref why) |
+            CompositerError::UpdateRepFetch(ref why) |
+            CompositerError::UpdateRepBranch(ref why) |
+            CompositerError::UpdateRepObject(ref why) |
+            CompositerError::UpdateRepReset(ref why) |
+            CompositerError::UpdateRepRevision(ref why) |
+            CompositerError::UpdateRepConfig(ref why) |
+            CompositerError::LockRepHead(ref why) |
+            CompositerError::LockRepCommit(ref why) |
+            CompositerError::Checksum(ref why) => {
+                write!(f, "{} {}", self.description(), why)
+            },
+            CompositerError::Mount(ref why) |
+            CompositerError::Reload(ref why) |
+            CompositerError::UpdateLoad(ref why) |
+            CompositerError::Info(ref why) => {
+                write!(f, "{} {}", self.description(), why)
+            },
+            CompositerError::BuildExit(ref status) => {
+                write!(f, "{} {}", self.description(), status)
+            },
+            CompositerError::Io(ref why) => write!(f, "{}", why),
+            _ => write!(f, "{}", self.description()),
+        }
     }
 }
 
@@ -89,6 +298,12 @@ impl Error for CompositerError {
             CompositerError::RmDir(_) => "Can't remove the directory.",
             CompositerError::MkDirGit(_) => "Can't create the `git` sub-directory.",
             CompositerError::MkDirLib(_) => "Can't create the `Lib` sub-directory.",
+            CompositerError::MkDirRollback(_) => "Can't create the `rollback` sub-directory.",
+            CompositerError::MkDirLog(_) => "Can't create the `log` sub-directory.",
+            CompositerError::MkDirInclude(_) => "Can't create the `include` sub-directory.",
+            CompositerError::WriteHeader(_) => "Can't write the neko.h header.",
+            CompositerError::BuildLog(_) => "Can't write the build log.",
+            CompositerError::RollbackSwap(_) => "Can't swap the library with its previous version.",
             CompositerError::ReadDirGit(_) => "Can't read the `git` sub-directory.",
             CompositerError::ReadDirLib(_) => "Can't read the `Lib` sub-directory.",
             CompositerError::OpenDirLib(_) => "Can't open the `lib` sub-directory.",
@@ -96,8 +311,23 @@ impl Error for CompositerError {
                 "Can't read the `manifest` Neko.toml\
                                              file."
             }
+            CompositerError::ReadLock(_) => "Can't read the `lock` Neko.lock file.",
+            CompositerError::WriteLock(_) => "Can't write the `lock` Neko.lock file.",
+            CompositerError::ReadRegistry(_) => "Can't read the registry's entry.",
+            CompositerError::BundleWrite(_) => "Can't write the bundle's archive.",
+            CompositerError::BundleRead(_) => "Can't unpack the bundle's archive.",
+            CompositerError::ReadMount(_) => "Can't read the `mount` Neko.mount file.",
+            CompositerError::WriteMount(_) => "Can't write the `mount` Neko.mount file.",
+            CompositerError::ReadTrust(_) => "Can't read the `trust` Neko.trust file.",
+            CompositerError::WriteTrust(_) => "Can't write the `trust` Neko.trust file.",
+            CompositerError::Signature(_) => "Can't run gpg to verify the signature.",
+            CompositerError::ReadLib(_) => "Can't read the dynamic library.",
             CompositerError::Mount(_) => "Can't mount the dynamic library.",
+            CompositerError::Reload(_) => "Can't reload the dynamic library.",
+            CompositerError::UpdateLoad(_) => "Can't load the updated dynamic library.",
+            CompositerError::Info(_) => "Can't inspect the dynamic library.",
             CompositerError::InstallClone(_) => "Can't clone the repository",
+            CompositerError::RegistryClone(_) => "Can't clone the registry.",
             CompositerError::UpdateRepOpen(_) => "Can't update the repository.",
             CompositerError::UpdateRepOrigin(_) => {
                 "Can't found the origin from\
@@ -117,13 +347,66 @@ impl Error for CompositerError {
                                                 target identifiant."
             }
             CompositerError::UpdateRepReset(_) => "Can't reset the repository.",
+            CompositerError::UpdateRepRevision(_) => {
+                "Can't found the revision from \
+                                                  repository."
+            }
+            CompositerError::UpdateRepConfig(_) => {
+                "Can't remember the revision from \
+                                                repository."
+            }
+            CompositerError::LockRepHead(_) => {
+                "Can't found the commit from \
+                                            repository's head."
+            }
+            CompositerError::LockRepCommit(_) => {
+                "Can't found the locked commit from \
+                                              repository."
+            }
+            CompositerError::Checksum(_) => {
+                "Can't compute the checksum of the \
+                                         dynamic library."
+            }
             CompositerError::BuildCommand(_) => "Can't run the command.",
+            CompositerError::InstallLocal(_) => "Can't link the local directory.",
             CompositerError::BuildExit(_) => "The build haven't exited with success.",
+            CompositerError::BuildBackend(_) => "The build backend isn't supported:",
+            CompositerError::BuildArtifact => "Can't found the built dynamic library.",
+            CompositerError::DependencyCycle(_) => "The dependencies have a cycle:",
+            CompositerError::VersionConflict(..) => {
+                "No tagged version satisfies every \
+                                                 requirement of"
+            }
+            CompositerError::ParseVersion(_) => "Can't parse the version",
+            CompositerError::ManifestKey(..) => "The manifest has a unvalid value for",
+            CompositerError::ManifestNeko(_) => "The library requires at least the Neko",
+            CompositerError::ParseCapability(_) => "The capability isn't known:",
+            CompositerError::Untrusted(..) => "The repository isn't trusted, \
+                                               confirm with `neko trust`:",
+            CompositerError::TrustAccount(_) => "The account to trust must be named \
+                                                 with its host like `github.com/arukana`:",
+            CompositerError::Unsigned(_) => "The commit isn't signed:",
+            CompositerError::BadSignature(_) => "The signature isn't valid for the commit",
             CompositerError::NekoPath => "Can't found the $NEKO_PATH environement variable.",
-            CompositerError::ParseManifest => {
+            CompositerError::ParseManifest(_) => {
                 "Can't parse the `manifest` Neko.toml\
                                            file."
             }
+            CompositerError::ParseLock => "Can't parse the `lock` Neko.lock file.",
+            CompositerError::ParseMount => "Can't parse the `mount` Neko.mount file.",
+            CompositerError::ParseTrust => "Can't parse the `trust` Neko.trust file.",
+            CompositerError::ParseRegistry => "Can't parse the registry's entry.",
+            CompositerError::RegistryUnset => "Can't found the $NEKO_REGISTRY environement variable.",
+            CompositerError::RegistryName(_) => "Can't found the library from the registry.",
+            CompositerError::Dependents(_, _) => "The library is required by its dependents, \
+                                                  uninstall with `--force`:",
+            CompositerError::BundleLocked(_) => "The library to bundle isn't pinned to \
+                                                 the Neko.lock.",
+            CompositerError::BundleCargo(_) => "The cargo backend can't build without network \
+                                                the library of the bundle:",
+            CompositerError::BundleName(_) => "The library of the bundle isn't named \
+                                               like `account@repo`:",
+            CompositerError::Rollback => "Can't found a previous version of the library.",
             CompositerError::ParseInteger => "Can't parse a integer from the table.",
             CompositerError::UnmountPosition => "Can't found the position.",
             CompositerError::UnmountRemove => "Can't remove the index.",
@@ -132,6 +415,7 @@ impl Error for CompositerError {
                 "The dynamic library as already a\
                                            repository."
             },
+            CompositerError::InstallBusy => "A installation already runs in background.",
             CompositerError::Io(ref why) => why.description(),
         }
     }
@@ -145,20 +429,47 @@ impl Error for CompositerError {
             CompositerError::RmDir(ref why) |
             CompositerError::MkDirGit(ref why) |
             CompositerError::MkDirLib(ref why) |
+            CompositerError::MkDirRollback(ref why) |
+            CompositerError::MkDirLog(ref why) |
+            CompositerError::MkDirInclude(ref why) |
+            CompositerError::WriteHeader(ref why) |
+            CompositerError::BuildLog(ref why) |
+            CompositerError::RollbackSwap(ref why) |
             CompositerError::ReadDirGit(ref why) |
             CompositerError::ReadDirLib(ref why) |
             CompositerError::OpenDirLib(ref why) |
             CompositerError::BuildCommand(ref why) |
-            CompositerError::ReadManifest(ref why) => Some(why),
+            CompositerError::InstallLocal(ref why) |
+            CompositerError::ReadManifest(ref why) |
+            CompositerError::ReadLock(ref why) |
+            CompositerError::WriteLock(ref why) |
+            CompositerError::ReadRegistry(ref why) |
+            CompositerError::BundleWrite(ref why) |
+            CompositerError::BundleRead(ref why) |
+            CompositerError::ReadMount(ref why) |
+            CompositerError::WriteMount(ref why) |
+            CompositerError::ReadTrust(ref why) |
+            CompositerError::WriteTrust(ref why) |
+            CompositerError::Signature(ref why) |
+            CompositerError::ReadLib(ref why) => Some(why),
             CompositerError::InstallClone(ref why) |
+            CompositerError::RegistryClone(ref why) |
             CompositerError::UpdateRepOpen(ref why) |
             CompositerError::UpdateRepOrigin(ref why) |
             CompositerError::UpdateRepFetch(ref why) |
             CompositerError::UpdateRepBranch(ref why) |
             CompositerError::UpdateRepObject(ref why) |
-            CompositerError::UpdateRepReset(ref why) => Some(why),
+            CompositerError::UpdateRepReset(ref why) |
+            CompositerError::UpdateRepRevision(ref why) |
+            CompositerError::UpdateRepConfig(ref why) |
+            CompositerError::LockRepHead(ref why) |
+            CompositerError::LockRepCommit(ref why) |
+            CompositerError::Checksum(ref why) => Some(why),
             CompositerError::Io(ref why) => Some(why),
-            CompositerError::Mount(ref why) => Some(why),
+            CompositerError::Mount(ref why) |
+            CompositerError::Reload(ref why) |
+            CompositerError::UpdateLoad(ref why) |
+            CompositerError::Info(ref why) => Some(why),
             _ => None,
         }
     }
@@ -184,28 +495,79 @@ impl PartialEq for CompositerError {
             (&CompositerError::RmDir(_), &CompositerError::RmDir(_)) => true,
             (&CompositerError::MkDirGit(_), &CompositerError::MkDirGit(_)) => true,
             (&CompositerError::MkDirLib(_), &CompositerError::MkDirLib(_)) => true,
+            (&CompositerError::MkDirRollback(_), &CompositerError::MkDirRollback(_)) => true,
+            (&CompositerError::MkDirLog(_), &CompositerError::MkDirLog(_)) => true,
+            (&CompositerError::MkDirInclude(_), &CompositerError::MkDirInclude(_)) => true,
+            (&CompositerError::WriteHeader(_), &CompositerError::WriteHeader(_)) => true,
+            (&CompositerError::BuildLog(_), &CompositerError::BuildLog(_)) => true,
+            (&CompositerError::RollbackSwap(_), &CompositerError::RollbackSwap(_)) => true,
             (&CompositerError::ReadDirGit(_), &CompositerError::ReadDirGit(_)) => true,
             (&CompositerError::ReadDirLib(_), &CompositerError::ReadDirLib(_)) => true,
             (&CompositerError::OpenDirLib(_), &CompositerError::OpenDirLib(_)) => true,
             (&CompositerError::BuildCommand(_), &CompositerError::BuildCommand(_)) => true,
+            (&CompositerError::InstallLocal(_), &CompositerError::InstallLocal(_)) => true,
             (&CompositerError::ReadManifest(_), &CompositerError::ReadManifest(_)) => true,
+            (&CompositerError::ReadLock(_), &CompositerError::ReadLock(_)) => true,
+            (&CompositerError::WriteLock(_), &CompositerError::WriteLock(_)) => true,
+            (&CompositerError::ReadRegistry(_), &CompositerError::ReadRegistry(_)) => true,
+            (&CompositerError::BundleWrite(_), &CompositerError::BundleWrite(_)) => true,
+            (&CompositerError::BundleRead(_), &CompositerError::BundleRead(_)) => true,
+            (&CompositerError::ReadMount(_), &CompositerError::ReadMount(_)) => true,
+            (&CompositerError::WriteMount(_), &CompositerError::WriteMount(_)) => true,
+            (&CompositerError::ReadTrust(_), &CompositerError::ReadTrust(_)) => true,
+            (&CompositerError::WriteTrust(_), &CompositerError::WriteTrust(_)) => true,
+            (&CompositerError::Signature(_), &CompositerError::Signature(_)) => true,
+            (&CompositerError::ReadLib(_), &CompositerError::ReadLib(_)) => true,
             (&CompositerError::InstallClone(_), &CompositerError::InstallClone(_)) => true,
+            (&CompositerError::RegistryClone(_), &CompositerError::RegistryClone(_)) => true,
             (&CompositerError::UpdateRepOpen(_), &CompositerError::UpdateRepOpen(_)) => true,
             (&CompositerError::UpdateRepOrigin(_), &CompositerError::UpdateRepOrigin(_)) => true,
             (&CompositerError::UpdateRepFetch(_), &CompositerError::UpdateRepFetch(_)) => true,
             (&CompositerError::UpdateRepBranch(_), &CompositerError::UpdateRepBranch(_)) => true,
             (&CompositerError::UpdateRepObject(_), &CompositerError::UpdateRepObject(_)) => true,
             (&CompositerError::UpdateRepReset(_), &CompositerError::UpdateRepReset(_)) => true,
+            (&CompositerError::UpdateRepRevision(_), &CompositerError::UpdateRepRevision(_)) => true,
+            (&CompositerError::UpdateRepConfig(_), &CompositerError::UpdateRepConfig(_)) => true,
+            (&CompositerError::LockRepHead(_), &CompositerError::LockRepHead(_)) => true,
+            (&CompositerError::LockRepCommit(_), &CompositerError::LockRepCommit(_)) => true,
+            (&CompositerError::Checksum(_), &CompositerError::Checksum(_)) => true,
             (&CompositerError::Mount(_), &CompositerError::Mount(_)) => true,
+            (&CompositerError::Reload(_), &CompositerError::Reload(_)) => true,
+            (&CompositerError::UpdateLoad(_), &CompositerError::UpdateLoad(_)) => true,
+            (&CompositerError::Info(_), &CompositerError::Info(_)) => true,
             (&CompositerError::BuildExit(_), &CompositerError::BuildExit(_)) => true,
+            (&CompositerError::BuildBackend(ref backend), &CompositerError::BuildBackend(ref other)) => backend.eq(other),
+            (&CompositerError::BuildArtifact, &CompositerError::BuildArtifact) => true,
+            (&CompositerError::DependencyCycle(ref cycle), &CompositerError::DependencyCycle(ref other)) => cycle.eq(other),
+            (&CompositerError::VersionConflict(ref name, _), &CompositerError::VersionConflict(ref other, _)) => name.eq(other),
+            (&CompositerError::ParseVersion(ref version), &CompositerError::ParseVersion(ref other)) => version.eq(other),
+            (&CompositerError::ManifestKey(ref key, _), &CompositerError::ManifestKey(ref other, _)) => key.eq(other),
+            (&CompositerError::ManifestNeko(ref version), &CompositerError::ManifestNeko(ref other)) => version.eq(other),
+            (&CompositerError::ParseCapability(ref a), &CompositerError::ParseCapability(ref b)) => a.eq(b),
+            (&CompositerError::Untrusted(ref a, ref c, ref e), &CompositerError::Untrusted(ref b, ref d, ref f)) => a.eq(b) && c.eq(d) && e.eq(f),
+            (&CompositerError::TrustAccount(ref a), &CompositerError::TrustAccount(ref b)) => a.eq(b),
+            (&CompositerError::Unsigned(ref a), &CompositerError::Unsigned(ref b)) => a.eq(b),
+            (&CompositerError::BadSignature(ref a), &CompositerError::BadSignature(ref b)) => a.eq(b),
             (&CompositerError::UpdateRepBranchId, &CompositerError::UpdateRepBranchId) => true,
             (&CompositerError::NekoPath, &CompositerError::NekoPath) => true,
             (&CompositerError::UnmountPosition, &CompositerError::UnmountPosition) => true,
             (&CompositerError::UnmountRemove, &CompositerError::UnmountRemove) => true,
-            (&CompositerError::ParseManifest, &CompositerError::ParseManifest) => true,
+            (&CompositerError::ParseManifest(_), &CompositerError::ParseManifest(_)) => true,
+            (&CompositerError::ParseLock, &CompositerError::ParseLock) => true,
+            (&CompositerError::ParseMount, &CompositerError::ParseMount) => true,
+            (&CompositerError::ParseTrust, &CompositerError::ParseTrust) => true,
+            (&CompositerError::ParseRegistry, &CompositerError::ParseRegistry) => true,
+            (&CompositerError::RegistryUnset, &CompositerError::RegistryUnset) => true,
+            (&CompositerError::RegistryName(ref a), &CompositerError::RegistryName(ref b)) => a.eq(b),
+            (&CompositerError::BundleLocked(ref a), &CompositerError::BundleLocked(ref b)) => a.eq(b),
+            (&CompositerError::BundleCargo(ref a), &CompositerError::BundleCargo(ref b)) => a.eq(b),
+            (&CompositerError::BundleName(ref a), &CompositerError::BundleName(ref b)) => a.eq(b),
+            (&CompositerError::Dependents(ref a, ref c), &CompositerError::Dependents(ref b, ref d)) => a.eq(b) && c.eq(d),
+            (&CompositerError::Rollback, &CompositerError::Rollback) => true,
             (&CompositerError::ParseInteger, &CompositerError::ParseInteger) => true,
             (&CompositerError::InstallFormat, &CompositerError::InstallFormat) => true,
             (&CompositerError::InstallExists, &CompositerError::InstallExists) => true,
+            (&CompositerError::InstallBusy, &CompositerError::InstallBusy) => true,
             _ => false,
         }
     }
@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::err::{CompositerError, Result};
+
+/// The struct `Graph` is a dictionary of library names and
+/// the library names of their dependencies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    edges: BTreeMap<String, Vec<String>>,
+}
+
+impl Graph {
+    /// The method `contains` returns true if the library
+    /// has been inserted.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn contains(&self, libraryname: &str) -> bool {
+        self.edges.contains_key(libraryname)
+    }
+
+    /// The method `insert` adds a library and the library names
+    /// of its dependencies.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn insert(&mut self, libraryname: String, dependencies: Vec<String>) {
+        self.edges.insert(libraryname, dependencies);
+    }
+
+    /// The accessor method `get_dependencies` returns the library names
+    /// of the dependencies from a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn get_dependencies(&self, libraryname: &str) -> &[String] {
+        self.edges.get(libraryname)
+                  .map(|dependencies| &dependencies[..])
+                  .unwrap_or_default()
+    }
+
+    /// The accessor method `get_dependents` returns the library names
+    /// which depend on a library.
+    /// @ libraryname: `arukana@libmeow`.
+    pub fn get_dependents(&self, libraryname: &str) -> Vec<String> {
+        self.edges.iter()
+                  .filter(|&(dependent, dependencies)|
+                      dependent.ne(libraryname) &&
+                      dependencies.iter().any(|dependency| dependency.eq(libraryname)))
+                  .map(|(dependent, _)| dependent.clone())
+                  .collect::<Vec<String>>()
+    }
+
+    /// The method `order` returns the library names reachable from
+    /// a library in topological order, the dependencies before
+    /// their dependents, or the first found cycle.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn order(&self, libraryname: &str) -> Result<Vec<String>> {
+        let mut path: Vec<String> = Vec::new();
+        let mut visited: BTreeSet<String> = BTreeSet::new();
+        let mut order: Vec<String> = Vec::with_capacity(self.edges.len());
+
+        self.visit(libraryname, &mut path, &mut visited, &mut order)
+            .and(Ok(order))
+    }
+
+    /// The method `order_all` returns every library name in topological
+    /// order, the dependencies before their dependents, or the first
+    /// found cycle.
+    pub fn order_all(&self) -> Result<Vec<String>> {
+        let mut path: Vec<String> = Vec::new();
+        let mut visited: BTreeSet<String> = BTreeSet::new();
+        let mut order: Vec<String> = Vec::with_capacity(self.edges.len());
+
+        for libraryname in self.edges.keys() {
+            try!(self.visit(libraryname, &mut path, &mut visited, &mut order));
+        }
+        Ok(order)
+    }
+
+    /// The method `visit` walks in depth the dependencies of a library
+    /// and pushes the library after them.
+    fn visit(&self,
+             libraryname: &str,
+             path: &mut Vec<String>,
+             visited: &mut BTreeSet<String>,
+             order: &mut Vec<String>)
+             -> Result<()> {
+        if visited.contains(libraryname) {
+            Ok(())
+        } else if let Some(index) = path.iter()
+                                        .position(|name| name.eq(libraryname)) {
+            let mut cycle: Vec<String> = path[index..].to_vec();
+
+            cycle.push(libraryname.to_string());
+            Err(CompositerError::DependencyCycle(cycle))
+        } else {
+            path.push(libraryname.to_string());
+            for dependency in self.get_dependencies(libraryname) {
+                try!(self.visit(dependency, path, visited, order));
+            }
+            path.pop();
+            visited.insert(libraryname.to_string());
+            order.push(libraryname.to_string());
+            Ok(())
+        }
+    }
+}
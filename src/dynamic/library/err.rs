@@ -2,6 +2,10 @@ use std::error::Error;
 use std::fmt;
 use std::io;
 
+use ::libc;
+
+use super::SPEC_ABI_VERSION;
+
 pub type Result<T> = ::std::result::Result<T, LibraryError>;
 
 /// The enum `LibraryError` defines the possible errors
@@ -14,13 +18,28 @@ pub enum LibraryError {
     BadGet(io::Error),
     /// DynamicLibrary as occured an error.
     BadDyLib(String),
+    /// Can't copy the dynamic library.
+    BadCopy(io::Error),
+    /// The library doesn't export the `neko_abi_version` symbol.
+    NoAbi,
+    /// The library is built for a other layout's version.
+    BadAbi(libc::c_uint),
 }
 
 impl fmt::Display for LibraryError {
     /// The function `fmt` formats the value using
     /// the given formatter.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self)
+        match *self {
+            LibraryError::BadAbi(version) => {
+                write!(f, "{} ({} instead of {})", self.description(),
+                       version, SPEC_ABI_VERSION)
+            },
+            LibraryError::BadDyLib(ref why) => write!(f, "{} {}", self.description(), why),
+            LibraryError::BadGet(ref why) |
+            LibraryError::BadCopy(ref why) => write!(f, "{} {}", self.description(), why),
+            _ => write!(f, "{}", self.description()),
+        }
     }
 }
 
@@ -32,6 +51,9 @@ impl Error for LibraryError {
             LibraryError::EmptyEvent => "Can't found any methods.",
             LibraryError::BadGet(_) => "Can't get the symbole from dy library.",
             LibraryError::BadDyLib(_) => "DynamicLibrary as occured an error.",
+            LibraryError::BadCopy(_) => "Can't copy the dynamic library.",
+            LibraryError::NoAbi => "The library doesn't export the `neko_abi_version` symbol.",
+            LibraryError::BadAbi(_) => "The library is built for a other layout's version.",
         }
     }
 
@@ -39,7 +61,8 @@ impl Error for LibraryError {
     /// this error if any.
     fn cause(&self) -> Option<&Error> {
         match *self {
-            LibraryError::BadGet(ref why) => Some(why),
+            LibraryError::BadGet(ref why) |
+            LibraryError::BadCopy(ref why) => Some(why),
             _ => None,
         }
     }
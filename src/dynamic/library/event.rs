@@ -0,0 +1,273 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::slice;
+
+use ::libc;
+use ::pty;
+
+use super::SPEC_HOOKS;
+
+/// The version of the `Event`'s layout, to increment when
+/// a kind or a payload is added.
+pub const SPEC_EVENT_VERSION: libc::c_uint = 1;
+/// The name of the function which receives every event.
+pub const SPEC_EVENT_HOOK: &'static str = "on_event";
+/// The symbol of the function which receives every event.
+pub const SPEC_EVENT_SYMBOL: &'static [u8] = b"on_event\0";
+/// The number of words reserved by the payload for the next kinds.
+const SPEC_EVENT_RESERVED: usize = 8;
+
+/// The enum `Kind` is the hook of a event, a library
+/// must ignore the kinds which it doesn't know.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Install = 0,
+    Uninstall = 1,
+    Start = 2,
+    End = 3,
+    Idle = 4,
+    Process = 5,
+    Command = 6,
+    KeyUnicodeDown = 7,
+    KeyStringDown = 8,
+    KeyRepeatDown = 9,
+    KeyIntervalDown = 10,
+    MousePressed = 11,
+    MouseReleased = 12,
+    Input = 13,
+    Output = 14,
+    Resized = 15,
+}
+
+/// The list of every kind of event.
+pub const SPEC_KINDS: [Kind; 16] = [
+    Kind::Install, Kind::Uninstall, Kind::Start, Kind::End, Kind::Idle,
+    Kind::Process, Kind::Command, Kind::KeyUnicodeDown, Kind::KeyStringDown,
+    Kind::KeyRepeatDown, Kind::KeyIntervalDown, Kind::MousePressed,
+    Kind::MouseReleased, Kind::Input, Kind::Output, Kind::Resized,
+];
+
+impl Kind {
+    /// The accessor method `get_name` returns the name of the hook
+    /// like `key_string_down`.
+    pub fn get_name(&self) -> &'static str {
+        SPEC_HOOKS[*self as usize]
+    }
+}
+
+/// The struct `Text` is a text with its length, without null character.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Text {
+    bytes: *const libc::c_uchar,
+    len: libc::size_t,
+}
+
+impl<'a> From<&'a [libc::c_uchar]> for Text {
+    fn from(text: &'a [libc::c_uchar]) -> Text {
+        Text {
+            bytes: text.as_ptr(),
+            len: text.len(),
+        }
+    }
+}
+
+/// The struct `Process` is the name and the pid of the child current process.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Process {
+    name: Text,
+    pid: libc::c_int,
+}
+
+/// The struct `Mouse` is the code and the coordinate of a click.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Mouse {
+    code: libc::c_uint,
+    xy: [libc::c_ushort; 2],
+}
+
+/// The union `Payload` is the argument of a event, chosen by its kind.
+#[repr(C)]
+#[derive(Clone, Copy)]
+union Payload {
+    /// `Command`, `KeyStringDown`, `Input` and `Output`.
+    text: Text,
+    process: Process,
+    key_unicode: libc::c_ulonglong,
+    key_repeat: libc::c_ulong,
+    key_interval: libc::c_longlong,
+    /// `MousePressed` and `MouseReleased`.
+    mouse: Mouse,
+    size: pty::Winszed,
+    /// The room of the next payloads, which keeps the size of the layout.
+    reserved: [u64; SPEC_EVENT_RESERVED],
+}
+
+/// The struct `Event` is given to the extern function `on_event`
+/// which replaces every hook, the texts carry their length.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Event<'a> {
+    version: libc::c_uint,
+    kind: libc::c_uint,
+    payload: Payload,
+    text: PhantomData<&'a [libc::c_uchar]>,
+}
+
+impl<'a> Event<'a> {
+    /// The constructor method `with_payload` returns a event of a kind,
+    /// its payload is zeroed before being set.
+    fn with_payload<F: FnOnce(&mut Payload)>(kind: Kind, set: F) -> Self {
+        let mut event: Event = Event {
+            version: SPEC_EVENT_VERSION,
+            kind: kind as libc::c_uint,
+            payload: Payload { reserved: [0; SPEC_EVENT_RESERVED] },
+            text: PhantomData,
+        };
+
+        set(&mut event.payload);
+        event
+    }
+
+    /// The constructor method `text` returns a event which carries a text.
+    /// @ kind: `Kind::Output`.
+    /// @ text: `b"nya"`.
+    pub fn text(kind: Kind, text: &'a [libc::c_uchar]) -> Self {
+        Event::with_payload(kind, |payload| payload.text = Text::from(text))
+    }
+
+    /// The constructor method `process` returns a event of the child
+    /// current process.
+    /// @ name: `b"vim"`.
+    pub fn process(name: &'a [libc::c_uchar], pid: libc::c_int) -> Self {
+        Event::with_payload(Kind::Process, |payload| payload.process = Process {
+            name: Text::from(name),
+            pid: pid,
+        })
+    }
+
+    /// The constructor method `key_unicode_down` returns a event of a pressed key.
+    pub fn key_unicode_down(code: libc::c_ulonglong) -> Self {
+        Event::with_payload(Kind::KeyUnicodeDown, |payload| payload.key_unicode = code)
+    }
+
+    /// The constructor method `key_repeat_down` returns a event of a held key.
+    pub fn key_repeat_down(repeat: libc::c_ulong) -> Self {
+        Event::with_payload(Kind::KeyRepeatDown, |payload| payload.key_repeat = repeat)
+    }
+
+    /// The constructor method `key_interval_down` returns a event between two
+    /// repetitions of the held key.
+    pub fn key_interval_down(interval: libc::c_longlong) -> Self {
+        Event::with_payload(Kind::KeyIntervalDown, |payload| payload.key_interval = interval)
+    }
+
+    /// The constructor method `mouse` returns a event of the mouse.
+    /// @ kind: `Kind::MousePressed`.
+    pub fn mouse(kind: Kind, code: libc::c_uint, xy: [libc::c_ushort; 2]) -> Self {
+        Event::with_payload(kind, |payload| payload.mouse = Mouse {
+            code: code,
+            xy: xy,
+        })
+    }
+
+    /// The constructor method `resized` returns a event of the window's size.
+    pub fn resized(size: &pty::Winszed) -> Self {
+        Event::with_payload(Kind::Resized, |payload| payload.size = *size)
+    }
+
+    pub fn get_version(&self) -> libc::c_uint {
+        self.version
+    }
+
+    /// The accessor method `get_kind` returns the kind of the event
+    /// or None if it's unknown.
+    pub fn get_kind(&self) -> Option<Kind> {
+        SPEC_KINDS.iter()
+                  .find(|kind| (**kind as libc::c_uint).eq(&self.kind))
+                  .cloned()
+    }
+
+    /// The accessor method `get_text` returns the text of a `Command`,
+    /// `KeyStringDown`, `Input` or `Output` event.
+    pub fn get_text(&self) -> Option<&'a [libc::c_uchar]> {
+        match self.get_kind() {
+            Some(Kind::Command) | Some(Kind::KeyStringDown) |
+            Some(Kind::Input) | Some(Kind::Output) => unsafe {
+                Some(slice::from_raw_parts(self.payload.text.bytes, self.payload.text.len))
+            },
+            _ => None,
+        }
+    }
+
+    /// The accessor method `get_process` returns the name and the pid
+    /// of a `Process` event.
+    pub fn get_process(&self) -> Option<(&'a [libc::c_uchar], libc::c_int)> {
+        match self.get_kind() {
+            Some(Kind::Process) => unsafe {
+                Some((slice::from_raw_parts(self.payload.process.name.bytes,
+                                            self.payload.process.name.len),
+                      self.payload.process.pid))
+            },
+            _ => None,
+        }
+    }
+
+    pub fn get_key_unicode_down(&self) -> Option<libc::c_ulonglong> {
+        match self.get_kind() {
+            Some(Kind::KeyUnicodeDown) => unsafe { Some(self.payload.key_unicode) },
+            _ => None,
+        }
+    }
+
+    pub fn get_key_repeat_down(&self) -> Option<libc::c_ulong> {
+        match self.get_kind() {
+            Some(Kind::KeyRepeatDown) => unsafe { Some(self.payload.key_repeat) },
+            _ => None,
+        }
+    }
+
+    pub fn get_key_interval_down(&self) -> Option<libc::c_longlong> {
+        match self.get_kind() {
+            Some(Kind::KeyIntervalDown) => unsafe { Some(self.payload.key_interval) },
+            _ => None,
+        }
+    }
+
+    /// The accessor method `get_mouse` returns the code and the coordinate
+    /// of a `MousePressed` or `MouseReleased` event.
+    pub fn get_mouse(&self) -> Option<(libc::c_uint, [libc::c_ushort; 2])> {
+        match self.get_kind() {
+            Some(Kind::MousePressed) | Some(Kind::MouseReleased) => unsafe {
+                Some((self.payload.mouse.code, self.payload.mouse.xy))
+            },
+            _ => None,
+        }
+    }
+
+    pub fn get_resized(&self) -> Option<&pty::Winszed> {
+        match self.get_kind() {
+            Some(Kind::Resized) => unsafe { Some(&self.payload.size) },
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<Kind> for Event<'a> {
+    /// The constructor `from` returns a event without payload
+    /// like `Kind::Start`.
+    fn from(kind: Kind) -> Event<'a> {
+        Event::with_payload(kind, |_| {})
+    }
+}
+
+impl<'a> fmt::Debug for Event<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Event {{ version: {}, kind: {:?} }}",
+               self.version,
+               self.get_kind())
+    }
+}
@@ -0,0 +1,252 @@
+use std::mem;
+
+use ::graphic;
+use ::pty;
+
+use super::SPEC_ABI_VERSION;
+use super::host::{self, SPEC_HOST_VERSION};
+use super::event::{Event, SPEC_EVENT_VERSION, SPEC_KINDS};
+use super::state::LibraryState;
+use super::state::Relative;
+use super::state::tooltip::Tooltip;
+use super::state::persona::{Persona, Position, Cardinal};
+
+/// The name of the C header of the libraries.
+pub const SPEC_HEADER: &'static str = "neko.h";
+
+/// The function `get_character` returns the C layout of a `pty::Character`,
+/// its glyph is the first field and the bytes after are its attributes.
+fn get_character() -> String {
+    format!("    uint32_t glyph;\n    unsigned char attribute[{}];\n",
+            mem::size_of::<pty::Character>() - mem::size_of::<u32>())
+}
+
+/// The function `get_enum` returns the constants of a enumeration.
+/// @ name: `NEKO_RELATIVE`.
+/// @ variants: `[("TOP", 0), ("BOTTOM", 1)]`.
+fn get_enum(name: &str, variants: &[(&str, u32)]) -> String {
+    variants.iter()
+            .map(|&(variant, value)| format!("#define {}_{} {}\n", name, variant, value))
+            .collect::<String>()
+}
+
+/// The function `get_kind` returns the constants of the events' kinds
+/// named by their hooks like `NEKO_EVENT_KEY_STRING_DOWN`.
+fn get_kind() -> String {
+    SPEC_KINDS.iter()
+              .map(|kind| format!("#define NEKO_EVENT_{} {}\n",
+                                  kind.get_name().to_uppercase(),
+                                  *kind as u32))
+              .collect::<String>()
+}
+
+/// The function `generate` returns the neko.h header with the layouts
+/// of the running Neko, the hooks and the checks of their sizes.
+/// It's written by `Compositer::header` instead of the build script,
+/// because the sizes come from the compiled layouts of Neko and of
+/// pty-proc which a build script can't load.
+pub fn generate() -> String {
+    let tooltip: Tooltip = Tooltip::default();
+
+    format!("\
+/* neko.h is generated by Neko, don't edit it. */
+#ifndef NEKO_H
+# define NEKO_H
+
+# include <stddef.h>
+# include <stdint.h>
+
+# define NEKO_ABI_VERSION {abi}
+# define NEKO_HOST_VERSION {host_version}
+# define NEKO_EVENT_VERSION {event_version}
+# define NEKO_MAX_DRAW {max_draw}
+# define NEKO_MAX_XY {max_xy}
+# define NEKO_TOOLTIP_LEN {tooltip_len}
+
+{cardinal}
+typedef uint32_t NekoCardinal;
+
+{relative}
+typedef uint32_t NekoRelative;
+
+typedef uint32_t NekoSheet;
+
+typedef struct NekoTuple {{
+    uint32_t part;
+    uint32_t emotion;
+}} NekoTuple;
+
+typedef struct NekoPosition {{
+    NekoCardinal cardinal;
+    unsigned short cartesian[2];
+}} NekoPosition;
+
+typedef struct NekoPersona {{
+    NekoSheet sheet;
+    NekoTuple emotion[NEKO_MAX_DRAW][NEKO_MAX_XY];
+    NekoPosition position;
+}} NekoPersona;
+
+typedef struct NekoCharacter {{
+{character}}} NekoCharacter;
+
+typedef struct NekoTooltip {{
+    NekoRelative cardinal;
+    NekoCharacter message[NEKO_TOOLTIP_LEN];
+}} NekoTooltip;
+
+typedef struct NekoLibraryState {{
+    NekoPersona persona;
+    NekoTooltip tooltip;
+    unsigned char unmount;
+    unsigned char lock;
+}} NekoLibraryState;
+
+typedef struct NekoWinszed {{
+    unsigned short ws_row;
+    unsigned short ws_col;
+    unsigned short ws_xpixel;
+    unsigned short ws_ypixel;
+}} NekoWinszed;
+
+typedef struct NekoCartesian {{
+    unsigned short xy[2];
+}} NekoCartesian;
+
+/* save is the userdata of the library: start sets *save, every hook receives it
+   and end frees it, Neko forgets it after end. */
+typedef void (*NekoHook)(NekoLibraryState *state, void **save);
+typedef void (*NekoHookProcess)(NekoLibraryState *state, void **save,
+                                const unsigned char *name, int pid);
+typedef void (*NekoHookText)(NekoLibraryState *state, void **save,
+                             const unsigned char *text);
+typedef void (*NekoHookKeyUnicode)(NekoLibraryState *state, void **save,
+                                   unsigned long long code);
+typedef void (*NekoHookKeyRepeat)(NekoLibraryState *state, void **save,
+                                  unsigned long repeat);
+typedef void (*NekoHookKeyInterval)(NekoLibraryState *state, void **save,
+                                    long long interval);
+typedef void (*NekoHookMouse)(NekoLibraryState *state, void **save,
+                              unsigned int code, NekoCartesian xy);
+typedef void (*NekoHookResized)(NekoLibraryState *state, void **save,
+                                const NekoWinszed *size);
+
+/* The table of the host's functions given to the optional neko_host before start,
+   it's freed once the library is ended and closed so it mustn't be used after end,
+   and every function returns 0 or -1 when it's refused. */
+typedef struct NekoHost {{
+    unsigned int version;
+    const void *context;
+    int (*set_tooltip)(const struct NekoHost *host, NekoLibraryState *state, const unsigned char *text, size_t len);
+    int (*set_tooltip_cardinal)(const struct NekoHost *host, NekoLibraryState *state, NekoRelative cardinal);
+    int (*set_sheet)(const struct NekoHost *host, NekoLibraryState *state, const unsigned char *name, size_t len);
+    int (*set_emotion)(const struct NekoHost *host, NekoLibraryState *state, const NekoTuple *emotion, size_t len);
+    int (*set_position_cardinal)(const struct NekoHost *host, NekoLibraryState *state, NekoCardinal cardinal);
+    int (*set_position_cartesian)(const struct NekoHost *host, NekoLibraryState *state, NekoCartesian xy);
+    int (*write)(const struct NekoHost *host, const unsigned char *bytes, size_t len);
+    int (*get_cell)(const struct NekoHost *host, unsigned short x, unsigned short y,
+                    NekoCharacter *cell);
+    int (*unmount)(const struct NekoHost *host);
+    int (*log)(const struct NekoHost *host, const unsigned char *text, size_t len);
+}} NekoHost;
+
+typedef void (*NekoHookHost)(const NekoHost *host);
+
+{kind}
+/* The texts of a NekoEvent carry their length and aren't ended by a null character. */
+typedef struct NekoText {{
+    const unsigned char *bytes;
+    size_t len;
+}} NekoText;
+
+typedef struct NekoProcess {{
+    NekoText name;
+    int pid;
+}} NekoProcess;
+
+typedef struct NekoMouse {{
+    unsigned int code;
+    unsigned short xy[2];
+}} NekoMouse;
+
+typedef union NekoPayload {{
+    NekoText text;
+    NekoProcess process;
+    unsigned long long key_unicode;
+    unsigned long key_repeat;
+    long long key_interval;
+    NekoMouse mouse;
+    NekoWinszed size;
+    uint64_t reserved[8];
+}} NekoPayload;
+
+/* on_event is called instead of every hook when it's exported,
+   the kinds which aren't known by the library must be ignored. */
+typedef struct NekoEvent {{
+    unsigned int version;
+    uint32_t kind;
+    NekoPayload payload;
+}} NekoEvent;
+
+typedef void (*NekoHookEvent)(NekoLibraryState *state, void **save,
+                              const NekoEvent *event);
+
+/* The symbols looked up by Neko: neko_abi_version is required to be loaded,
+   neko_host and on_event are optional like the hooks typed above. */
+extern const unsigned int neko_abi_version;
+void neko_host(const NekoHost *host);
+void on_event(NekoLibraryState *state, void **save, const NekoEvent *event);
+
+_Static_assert(sizeof(NekoLibraryState) == {library_state}, \"NekoLibraryState\");
+_Static_assert(sizeof(NekoPosition) == {position}, \"NekoPosition\");
+_Static_assert(sizeof(NekoTooltip) == {tooltip}, \"NekoTooltip\");
+_Static_assert(sizeof(NekoPersona) == {persona}, \"NekoPersona\");
+_Static_assert(sizeof(NekoSheet) == {sheet}, \"NekoSheet\");
+_Static_assert(sizeof(NekoTuple) == {tuple}, \"NekoTuple\");
+_Static_assert(sizeof(NekoRelative) == {relative_size}, \"NekoRelative\");
+_Static_assert(sizeof(NekoCardinal) == {cardinal_size}, \"NekoCardinal\");
+_Static_assert(sizeof(NekoCharacter) == {character_size}, \"NekoCharacter\");
+_Static_assert(sizeof(NekoWinszed) == {winszed}, \"NekoWinszed\");
+_Static_assert(sizeof(NekoHost) == {host}, \"NekoHost\");
+_Static_assert(sizeof(NekoEvent) == {event}, \"NekoEvent\");
+
+#endif
+",
+        abi = SPEC_ABI_VERSION,
+        host_version = SPEC_HOST_VERSION,
+        event_version = SPEC_EVENT_VERSION,
+        max_draw = graphic::SPEC_MAX_DRAW,
+        max_xy = graphic::SPEC_MAX_XY,
+        tooltip_len = tooltip.get_message().len(),
+        cardinal = get_enum("NEKO_CARDINAL", &[
+            ("UPPER_LEFT", Cardinal::UpperLeft as u32),
+            ("UPPER_MIDDLE", Cardinal::UpperMiddle as u32),
+            ("UPPER_RIGHT", Cardinal::UpperRight as u32),
+            ("MIDDLE_LEFT", Cardinal::MiddleLeft as u32),
+            ("MIDDLE_CENTRAL", Cardinal::MiddleCentral as u32),
+            ("MIDDLE_RIGHT", Cardinal::MiddleRight as u32),
+            ("LOWER_LEFT", Cardinal::LowerLeft as u32),
+            ("LOWER_MIDDLE", Cardinal::LowerMiddle as u32),
+            ("LOWER_RIGHT", Cardinal::LowerRight as u32),
+        ]),
+        relative = get_enum("NEKO_RELATIVE", &[
+            ("TOP", Relative::Top as u32),
+            ("BOTTOM", Relative::Bottom as u32),
+            ("RIGHT", Relative::Right as u32),
+            ("LEFT", Relative::Left as u32),
+        ]),
+        kind = get_kind(),
+        character = get_character(),
+        library_state = mem::size_of::<LibraryState>(),
+        position = mem::size_of::<Position>(),
+        tooltip = mem::size_of::<Tooltip>(),
+        persona = mem::size_of::<Persona>(),
+        sheet = mem::size_of::<graphic::Sheet>(),
+        tuple = mem::size_of::<graphic::Tuple>(),
+        relative_size = mem::size_of::<Relative>(),
+        cardinal_size = mem::size_of::<Cardinal>(),
+        character_size = mem::size_of::<pty::Character>(),
+        winszed = mem::size_of::<pty::Winszed>(),
+        host = mem::size_of::<host::Table>(),
+        event = mem::size_of::<Event>())
+}
@@ -0,0 +1,467 @@
+use std::borrow::Borrow;
+use std::cell::{Cell, RefCell, RefMut};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::ptr;
+use std::rc::Rc;
+use std::slice;
+use std::str;
+
+use ::graphic;
+use ::libc;
+use ::pty;
+
+use ::dynamic::capability::{Capability, Capabilities};
+
+use super::state::LibraryState;
+use super::state::Relative;
+use super::state::persona::{Position, Cardinal};
+
+/// The version of the host's table, to increment when
+/// a function is added to this table.
+pub const SPEC_HOST_VERSION: libc::c_uint = 1;
+/// The symbol of the function which receives the host's table.
+pub const SPEC_HOST_SYMBOL: &'static [u8] = b"neko_host\0";
+/// The returned value of a host's function which succeeds.
+const SPEC_HOST_OK: libc::c_int = 0;
+/// The returned value of a host's function which is refused.
+const SPEC_HOST_ERR: libc::c_int = -1;
+
+/// The struct `Host` is shared by the tables of the libraries,
+/// it keeps the bytes to write to the pty and gives the screen.
+pub struct Host {
+    /// The bytes written by the libraries to the pty.
+    input: RefCell<Vec<u8>>,
+    /// The screen of the pty during the events.
+    screen: Cell<*const pty::Display>,
+    /// The number of columns of the screen.
+    columns: Cell<usize>,
+    /// The characters of the screen by line, read once by
+    /// the first `get_cell` of the events.
+    cells: RefCell<Option<Vec<pty::Character>>>,
+    /// The file where the libraries log like `$HOME/.neko/log/neko.log`.
+    log: Option<PathBuf>,
+}
+
+impl Host {
+    /// The constructor method `new` returns a Host which logs to a file.
+    /// @ log: `Some("$HOME/.neko/log/neko.log")`.
+    pub fn new(log: Option<PathBuf>) -> Self {
+        Host {
+            input: RefCell::new(Vec::new()),
+            screen: Cell::new(ptr::null()),
+            columns: Cell::new(0),
+            cells: RefCell::new(None),
+            log: log,
+        }
+    }
+
+    /// The method `with_screen` gives the screen to the libraries
+    /// while the function runs.
+    pub fn with_screen<F: FnOnce()>(&self, screen: &pty::Display, size: &pty::Winszed, f: F) {
+        self.screen.set(screen);
+        self.columns.set(size.get_col());
+        f();
+        self.screen.set(ptr::null());
+        *self.cells.borrow_mut() = None;
+    }
+
+    /// The method `take_input` returns and forgets the bytes
+    /// written by the libraries to the pty.
+    pub fn take_input(&self) -> Vec<u8> {
+        self.input.borrow_mut().drain(..).collect::<Vec<u8>>()
+    }
+}
+
+impl Default for Host {
+    fn default() -> Self {
+        Host::new(None)
+    }
+}
+
+impl fmt::Debug for Host {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Host {{ input: {}, log: {:?} }}",
+               self.input.borrow().len(),
+               self.log)
+    }
+}
+
+/// The struct `Context` is the library of a table.
+struct Context {
+    /// The name of the library like `arukana@libnya`.
+    name: String,
+    /// The capabilities granted to the library.
+    capabilities: Capabilities,
+    /// The library requested to be unmounted.
+    unmount: Cell<bool>,
+    host: Rc<Host>,
+}
+
+/// The struct `Table` is the versioned table of the host's functions
+/// given to a library by its `neko_host` function, every function
+/// returns zero or -1 when it's refused, the setters of the state
+/// need the persona.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Table {
+    version: libc::c_uint,
+    context: *const libc::c_void,
+    set_tooltip: extern fn(table: *const Table, state: *mut LibraryState, text: *const libc::c_uchar, len: libc::size_t) -> libc::c_int,
+    set_tooltip_cardinal: extern fn(table: *const Table, state: *mut LibraryState, cardinal: libc::c_uint) -> libc::c_int,
+    set_sheet: extern fn(table: *const Table, state: *mut LibraryState, name: *const libc::c_uchar, len: libc::size_t) -> libc::c_int,
+    set_emotion: extern fn(table: *const Table, state: *mut LibraryState, emotion: *const graphic::Tuple, len: libc::size_t) -> libc::c_int,
+    set_position_cardinal: extern fn(table: *const Table, state: *mut LibraryState, cardinal: libc::c_uint) -> libc::c_int,
+    set_position_cartesian: extern fn(table: *const Table, state: *mut LibraryState, xy: [libc::c_ushort; 2]) -> libc::c_int,
+    write: extern fn(table: *const Table, bytes: *const libc::c_uchar, len: libc::size_t) -> libc::c_int,
+    get_cell: extern fn(table: *const Table, x: libc::c_ushort, y: libc::c_ushort, cell: *mut pty::Character) -> libc::c_int,
+    unmount: extern fn(table: *const Table) -> libc::c_int,
+    log: extern fn(table: *const Table, text: *const libc::c_uchar, len: libc::size_t) -> libc::c_int,
+}
+
+impl Table {
+    pub fn get_version(&self) -> libc::c_uint {
+        self.version
+    }
+
+    /// The method `set_tooltip` writes a text to the tooltip.
+    /// @ text: `nya`.
+    pub fn set_tooltip(&self, state: &mut LibraryState, text: &str) -> bool {
+        (self.set_tooltip)(self, state, text.as_ptr(), text.len()).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `set_tooltip_cardinal` moves the tooltip around the persona.
+    pub fn set_tooltip_cardinal(&self, state: &mut LibraryState, cardinal: Relative) -> bool {
+        (self.set_tooltip_cardinal)(self, state, cardinal as libc::c_uint).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `set_sheet` changes the persona's sheet.
+    /// @ name: `Bust`.
+    pub fn set_sheet(&self, state: &mut LibraryState, name: &str) -> bool {
+        (self.set_sheet)(self, state, name.as_ptr(), name.len()).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `set_emotion` changes the persona's emotion.
+    pub fn set_emotion(&self, state: &mut LibraryState, emotion: &[graphic::Tuple]) -> bool {
+        (self.set_emotion)(self, state, emotion.as_ptr(), emotion.len()).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `set_position_cardinal` moves the persona to a cardinal.
+    pub fn set_position_cardinal(&self, state: &mut LibraryState, cardinal: Cardinal) -> bool {
+        (self.set_position_cardinal)(self, state, cardinal as libc::c_uint).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `set_position_cartesian` moves the persona to a coordinate.
+    pub fn set_position_cartesian(&self, state: &mut LibraryState, xy: [libc::c_ushort; 2]) -> bool {
+        (self.set_position_cartesian)(self, state, xy).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `write` writes bytes to the pty.
+    /// @ bytes: `b"ls\n"`.
+    pub fn write(&self, bytes: &[u8]) -> bool {
+        (self.write)(self, bytes.as_ptr(), bytes.len()).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `get_cell` returns a character of the screen.
+    pub fn get_cell(&self, x: libc::c_ushort, y: libc::c_ushort) -> Option<pty::Character> {
+        let mut cell: pty::Character = pty::Character::from('\0');
+
+        if (self.get_cell)(self, x, y, &mut cell).eq(&SPEC_HOST_OK) {
+            Some(cell)
+        } else {
+            None
+        }
+    }
+
+    /// The method `unmount` requests to unmount the library.
+    pub fn unmount(&self) -> bool {
+        (self.unmount)(self).eq(&SPEC_HOST_OK)
+    }
+
+    /// The method `log` appends a line to the log of the libraries.
+    /// @ text: `meow`.
+    pub fn log(&self, text: &str) -> bool {
+        (self.log)(self, text.as_ptr(), text.len()).eq(&SPEC_HOST_OK)
+    }
+}
+
+impl fmt::Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Table {{ version: {} }}", self.version)
+    }
+}
+
+/// The struct `Binding` owns the context of a library and its table,
+/// both are freed with the binding after the library is ended and
+/// closed, so the library mustn't keep the table after its `end`.
+pub struct Binding {
+    table: Box<Table>,
+    context: Box<Context>,
+}
+
+impl Binding {
+    /// The constructor method `new` returns the table of a library.
+    /// @ name: `arukana@libnya`.
+    pub fn new(name: String, capabilities: Capabilities, host: Rc<Host>) -> Self {
+        let context: Box<Context> = Box::new(Context {
+            name: name,
+            capabilities: capabilities,
+            unmount: Cell::new(false),
+            host: host,
+        });
+        let address: *const Context = &*context;
+
+        Binding {
+            table: Box::new(Table {
+                version: SPEC_HOST_VERSION,
+                context: address as *const libc::c_void,
+                set_tooltip: set_tooltip,
+                set_tooltip_cardinal: set_tooltip_cardinal,
+                set_sheet: set_sheet,
+                set_emotion: set_emotion,
+                set_position_cardinal: set_position_cardinal,
+                set_position_cartesian: set_position_cartesian,
+                write: write,
+                get_cell: get_cell,
+                unmount: unmount,
+                log: log,
+            }),
+            context: context,
+        }
+    }
+
+    pub fn as_table(&self) -> &Table {
+        &self.table
+    }
+
+    /// The method `is_unmounting` returns true if the library
+    /// requested to be unmounted.
+    pub fn is_unmounting(&self) -> bool {
+        self.context.unmount.get()
+    }
+}
+
+impl fmt::Debug for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Binding {{ name: {}, unmount: {} }}",
+               self.context.name,
+               self.context.unmount.get())
+    }
+}
+
+/// The function `get_bytes` returns the bytes given to a host's function.
+unsafe fn get_bytes<'a>(bytes: *const libc::c_uchar, len: libc::size_t) -> Option<&'a [u8]> {
+    if bytes.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(bytes, len))
+    }
+}
+
+/// The function `get_context` returns the library of a table.
+unsafe fn get_context<'a>(table: *const Table) -> Option<&'a Context> {
+    table.as_ref().and_then(|table| (table.context as *const Context).as_ref())
+}
+
+/// The function `get_persona` returns the state changed by a library
+/// which is granted the persona.
+unsafe fn get_persona<'a>(table: *const Table, state: *mut LibraryState) -> Option<&'a mut LibraryState> {
+    match get_context(table) {
+        Some(context) if context.capabilities.contains(Capability::Persona) => state.as_mut(),
+        _ => None,
+    }
+}
+
+/// The function `get_relative` returns the tooltip's cardinal of a number.
+fn get_relative(cardinal: libc::c_uint) -> Option<Relative> {
+    [Relative::Top, Relative::Bottom, Relative::Right, Relative::Left]
+        .iter()
+        .find(|relative| (**relative as libc::c_uint).eq(&cardinal))
+        .cloned()
+}
+
+/// The function `get_cardinal` returns the persona's cardinal of a number.
+fn get_cardinal(cardinal: libc::c_uint) -> Option<Cardinal> {
+    [Cardinal::UpperLeft, Cardinal::UpperMiddle, Cardinal::UpperRight,
+     Cardinal::MiddleLeft, Cardinal::MiddleCentral, Cardinal::MiddleRight,
+     Cardinal::LowerLeft, Cardinal::LowerMiddle, Cardinal::LowerRight]
+        .iter()
+        .find(|position| (**position as libc::c_uint).eq(&cardinal))
+        .cloned()
+}
+
+/// The function `set_tooltip` writes a utf-8 text to the tooltip,
+/// the library must be granted the persona.
+extern fn set_tooltip(table: *const Table, state: *mut LibraryState, text: *const libc::c_uchar, len: libc::size_t) -> libc::c_int {
+    unsafe {
+        match (get_persona(table, state), get_bytes(text, len).and_then(|text| str::from_utf8(text).ok())) {
+            (Some(state), Some(text)) if text.chars().count() <= state.get_tooltip().get_message().len() => {
+                state.set_tooltip_message(text.to_string());
+                SPEC_HOST_OK
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `set_tooltip_cardinal` moves the tooltip around the persona.
+extern fn set_tooltip_cardinal(table: *const Table, state: *mut LibraryState, cardinal: libc::c_uint) -> libc::c_int {
+    unsafe {
+        match (get_persona(table, state), get_relative(cardinal)) {
+            (Some(state), Some(cardinal)) => {
+                state.set_tooltip_cardinal(cardinal);
+                SPEC_HOST_OK
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `set_sheet` changes the persona's sheet by its name like `Bust`.
+extern fn set_sheet(table: *const Table, state: *mut LibraryState, name: *const libc::c_uchar, len: libc::size_t) -> libc::c_int {
+    unsafe {
+        match (get_persona(table, state), get_bytes(name, len).and_then(|name| str::from_utf8(name).ok())) {
+            (Some(state), Some(name)) => match graphic::Sheet::new(name) {
+                Ok(sheet) => {
+                    state.set_persona_sheet(sheet);
+                    SPEC_HOST_OK
+                },
+                Err(_) => SPEC_HOST_ERR,
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `set_emotion` changes the persona's emotion,
+/// the table must have every tuple of the persona.
+extern fn set_emotion(table: *const Table, state: *mut LibraryState, emotion: *const graphic::Tuple, len: libc::size_t) -> libc::c_int {
+    unsafe {
+        match get_persona(table, state) {
+            Some(state) if emotion.is_null().not() &&
+                           len.eq(&(graphic::SPEC_MAX_DRAW * graphic::SPEC_MAX_XY)) => {
+                let mut persona = *state.get_persona();
+                let mut tuples = *persona.get_emotion();
+
+                tuples.iter_mut()
+                      .flat_map(|draw| draw.iter_mut())
+                      .zip(slice::from_raw_parts(emotion, len))
+                      .all(|(tuple, new)| {
+                          *tuple = *new;
+                          true
+                      });
+                persona.set_emotion(tuples);
+                state.set_persona(persona);
+                SPEC_HOST_OK
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `set_position_cardinal` moves the persona to a cardinal.
+extern fn set_position_cardinal(table: *const Table, state: *mut LibraryState, cardinal: libc::c_uint) -> libc::c_int {
+    unsafe {
+        match (get_persona(table, state), get_cardinal(cardinal)) {
+            (Some(state), Some(cardinal)) => {
+                state.set_persona_position(Position::from(cardinal));
+                SPEC_HOST_OK
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `set_position_cartesian` moves the persona to a coordinate.
+extern fn set_position_cartesian(table: *const Table, state: *mut LibraryState, xy: [libc::c_ushort; 2]) -> libc::c_int {
+    unsafe {
+        match get_persona(table, state) {
+            Some(state) => {
+                state.set_persona_position(Position::from(xy));
+                SPEC_HOST_OK
+            },
+            None => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `write` writes bytes to the pty after the event,
+/// the library must be granted the input.
+extern fn write(table: *const Table, bytes: *const libc::c_uchar, len: libc::size_t) -> libc::c_int {
+    unsafe {
+        match (get_context(table), get_bytes(bytes, len)) {
+            (Some(context), Some(bytes)) if context.capabilities.contains(Capability::Input) => {
+                context.host.input.borrow_mut().extend_from_slice(bytes);
+                SPEC_HOST_OK
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `get_cell` reads a character of the screen,
+/// the library must be granted the output.
+extern fn get_cell(table: *const Table, x: libc::c_ushort, y: libc::c_ushort, cell: *mut pty::Character) -> libc::c_int {
+    unsafe {
+        match (get_context(table), cell.as_mut()) {
+            (Some(context), Some(cell)) if context.capabilities.contains(Capability::Output) => {
+                let columns: usize = context.host.columns.get();
+                let mut cells: RefMut<Option<Vec<pty::Character>>> = context.host.cells.borrow_mut();
+
+                match context.host.screen.get().as_ref() {
+                    Some(screen) if (x as usize) < columns => {
+                        if cells.is_none() {
+                            *cells = Some(screen.into_iter()
+                                                .map(|character| *Borrow::<pty::Character>::borrow(&character))
+                                                .collect::<Vec<pty::Character>>());
+                        }
+                        match cells.as_ref().and_then(|cells| cells.get(y as usize * columns + x as usize)) {
+                            Some(character) => {
+                                *cell = *character;
+                                SPEC_HOST_OK
+                            },
+                            None => SPEC_HOST_ERR,
+                        }
+                    },
+                    _ => SPEC_HOST_ERR,
+                }
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `unmount` requests to unmount the library after the event.
+extern fn unmount(table: *const Table) -> libc::c_int {
+    unsafe {
+        match get_context(table) {
+            Some(context) => {
+                context.unmount.set(true);
+                SPEC_HOST_OK
+            },
+            None => SPEC_HOST_ERR,
+        }
+    }
+}
+
+/// The function `log` appends a line to the log of the libraries.
+extern fn log(table: *const Table, text: *const libc::c_uchar, len: libc::size_t) -> libc::c_int {
+    unsafe {
+        match (get_context(table), get_bytes(text, len)) {
+            (Some(context), Some(text)) => {
+                match context.host.log.as_ref().map(|path|
+                    OpenOptions::new().create(true).append(true).open(path).and_then(|mut descripter|
+                        writeln!(descripter, "{}: {}", context.name, String::from_utf8_lossy(text))
+                    )
+                ) {
+                    Some(Ok(())) => SPEC_HOST_OK,
+                    _ => SPEC_HOST_ERR,
+                }
+            },
+            _ => SPEC_HOST_ERR,
+        }
+    }
+}
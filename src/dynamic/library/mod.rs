@@ -1,84 +1,227 @@
 #[macro_use]
 mod macros;
 pub mod state;
+pub mod header;
+pub mod host;
+pub mod event;
 mod err;
 
+use std::cell::Cell;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::mem;
 use std::ptr;
+use std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering as AtomicOrdering};
 use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
 use std::cmp::{Eq, Ordering};
-use std::path::PathBuf;
-use std::ffi::CString;
-use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::ffi::{CStr, CString};
+use std::ops::{Deref, Not};
 
 pub use self::state::LibraryState;
+use self::state::persona::Persona;
+use self::state::tooltip::Tooltip;
 pub use self::err::{LibraryError, Result};
+use self::host::{Host, Binding};
+use self::event::{Event, Kind};
 
 use ::libc;
 use ::pty;
 
+use ::dynamic::capability::{Capability, Capabilities};
+
+/// The version of the `LibraryState`'s layout shared with the libraries,
+/// to increment when this layout changes.
+pub const SPEC_ABI_VERSION: libc::c_uint = 1;
+/// The symbol of the layout's version exported by a library.
+const SPEC_ABI_SYMBOL: &'static [u8] = b"neko_abi_version\0";
+
+/// The names of extern functions which a library can export.
+pub const SPEC_HOOKS: [&'static str; 16] = [
+    "install", "uninstall", "start", "end", "idle", "process", "command",
+    "key_unicode_down", "key_string_down", "key_repeat_down",
+    "key_interval_down", "mouse_pressed", "mouse_released",
+    "input", "output", "resized",
+];
+
+/// The generation of copied libraries.
+static SPEC_GENERATION: AtomicUsize = ATOMIC_USIZE_INIT;
+/// The mode of the private copies and of their directory.
+const SPEC_COPY_MODE: u32 = 0o700;
+/// The directory of the opened file descriptors.
+#[cfg(target_os = "linux")]
+const SPEC_FD_DIR: &'static str = "/proc/self/fd";
+/// The directory of the opened file descriptors.
+#[cfg(not(target_os = "linux"))]
+const SPEC_FD_DIR: &'static str = "/dev/fd";
+
 /// The struct `Library` is a table of callback.
 pub struct Library {
     /// `install` interface.
-    install: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void)>,
+    install: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void)>,
     /// `uninstall` interface.
-    uninstall: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void)>,
+    uninstall: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void)>,
     /// `mount` interface.
-    start: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void)>,
+    start: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void)>,
     /// `unmount` interface.
-    end: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void)>,
+    end: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void)>,
     /// `idle` interface.
-    idle: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void)>,
+    idle: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void)>,
     /// `process` interface.
-    process: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, name: *const libc::c_uchar, pid: libc::c_int)>,
+    process: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, name: *const libc::c_uchar, pid: libc::c_int)>,
     /// `command` interface.
-    command: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, line: *const libc::c_uchar)>,
+    command: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, line: *const libc::c_uchar)>,
     /// `key_unicode_down` interface.
-    key_unicode_down: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, code: libc::c_ulonglong)>,
+    key_unicode_down: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, code: libc::c_ulonglong)>,
     /// `key_string_down` interface.
-    key_string_down: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, text: *const libc::c_uchar)>,
+    key_string_down: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, text: *const libc::c_uchar)>,
     /// `key_repeat_down` interface.
-    key_repeat_down: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, repeat: libc::c_ulong)>,
+    key_repeat_down: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, repeat: libc::c_ulong)>,
     /// `key_interval_down` interface.
-    key_interval_down: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, interval: libc::c_longlong)>,
+    key_interval_down: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, interval: libc::c_longlong)>,
     /// 'mouse_pressed' interface.
-    mouse_pressed: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, code: libc::c_uint, xy: [libc::c_ushort; 2])>,
+    mouse_pressed: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, code: libc::c_uint, xy: [libc::c_ushort; 2])>,
     /// 'mouse_released' interface.
-    mouse_released: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, code: libc::c_uint, xy: [libc::c_ushort; 2])>,
+    mouse_released: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, code: libc::c_uint, xy: [libc::c_ushort; 2])>,
     /// `input` interface.
-    input: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, text: *const libc::c_uchar)>,
+    input: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, text: *const libc::c_uchar)>,
     /// `output` interface.
-    output: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, text: *const libc::c_uchar)>,
+    output: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, text: *const libc::c_uchar)>,
     /// `resized` interface.
-    resized: Option<extern fn(state: *const LibraryState, save: &*const *const libc::c_void, text: *const pty::Winszed)>,
-    /// `save` pointer to share a segment of librairy memory.
-    save: *const *const libc::c_void,
+    resized: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, text: *const pty::Winszed)>,
+    /// `on_event` interface, called instead of every hook when defined.
+    on_event: Option<extern fn(state: *mut LibraryState, save: *mut *mut libc::c_void, event: *const Event)>,
+    /// `save` userdata of the library, set by `start`, given to every
+    /// hook and freed by `end`.
+    save: Cell<*mut libc::c_void>,
     /// dynamic library interface.
     handle: *mut libc::c_void,
     /// priority queue.
     index: i64,
     /// Address of the library.
     path: PathBuf,
-    /// Inform to unmount the library.
-    unmounted: bool,
+    /// The private copy opened instead of the library, kept open
+    /// as long as its descriptor is loaded.
+    copy: Option<fs::File>,
+    /// The host's table given to the library.
+    binding: Binding,
+    /// The capabilities granted to the library.
+    capabilities: Capabilities,
 }
 
 impl Library {
-    /// The constructor method `new` returns a interface for a extern library.
-    pub fn new(path: PathBuf, index: i64, state: &LibraryState) -> Result<Self> {
+    /// The constructor method `new` returns a interface for a extern library
+    /// which receives only the events of its granted capabilities.
+    pub fn new(path: PathBuf,
+               index: i64,
+               capabilities: Capabilities,
+               host: &Rc<Host>,
+               state: &mut LibraryState)
+               -> Result<Self> {
+        Library::open(path, None, index, capabilities, host).map(|lib| {
+            lib.start(state);
+            lib
+        })
+    }
+
+    /// The constructor method `from_copy` returns a interface for a private
+    /// copy of a extern library, because the dynamic loader returns
+    /// the already opened handle for a same address. The copy is created
+    /// in a directory only readable by the user and loaded from its
+    /// descriptor, then unlinked. The copy isn't started, so the caller
+    /// may end the previous version first.
+    /// @ dir: `$HOME/.neko/copy`.
+    pub fn from_copy(path: PathBuf,
+                     dir: &Path,
+                     index: i64,
+                     capabilities: Capabilities,
+                     host: &Rc<Host>)
+                     -> Result<Self> {
+        let copy: PathBuf = dir.join(format!(
+            "{}-{}-{}",
+            unsafe { libc::getpid() },
+            SPEC_GENERATION.fetch_add(1, AtomicOrdering::SeqCst),
+            path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+        ));
+
+        match Library::copy(&path, dir, &copy) {
+            Err(why) => Err(LibraryError::BadCopy(why)),
+            Ok(descripter) => {
+                let library: Result<Self> =
+                    Library::open(path, Some(descripter), index, capabilities, host);
+
+                let _ = fs::remove_file(&copy);
+                library
+            },
+        }
+    }
+
+    /// The function `copy` writes the library to a new file of
+    /// a private directory and returns the written file.
+    /// @ path: `$HOME/.neko/lib/arukana@libnya.so`.
+    /// @ dir: `$HOME/.neko/copy`.
+    /// @ copy: `$HOME/.neko/copy/4242-0-arukana@libnya.so`.
+    fn copy(path: &Path, dir: &Path, copy: &Path) -> io::Result<fs::File> {
+        try!(fs::DirBuilder::new().recursive(true).mode(SPEC_COPY_MODE).create(dir));
+        if try!(fs::symlink_metadata(dir)).file_type().is_dir().not() {
+            return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+        }
+        try!(fs::set_permissions(dir, fs::Permissions::from_mode(SPEC_COPY_MODE)));
+
+        let mut descripter: fs::File = try!(fs::OpenOptions::new().read(true)
+                                                                  .write(true)
+                                                                  .create_new(true)
+                                                                  .mode(SPEC_COPY_MODE)
+                                                                  .open(copy));
+
+        match fs::File::open(path).and_then(|mut library|
+            io::copy(&mut library, &mut descripter)
+        ) {
+            Err(why) => {
+                let _ = fs::remove_file(copy);
+                Err(why)
+            },
+            Ok(_) => Ok(descripter),
+        }
+    }
+
+    /// The constructor method `open` loads the library or its copy
+    /// and gives it the host's table.
+    fn open(path: PathBuf,
+            copy: Option<fs::File>,
+            index: i64,
+            capabilities: Capabilities,
+            host: &Rc<Host>)
+            -> Result<Self> {
         unsafe {
             let mut libname: Vec<libc::c_uchar> = Vec::with_capacity(4096);
-            
-            libname.extend_from_slice(&path.as_os_str().as_bytes()[..]);
+            let address: PathBuf = copy.as_ref()
+                                       .map(|descripter| Path::new(SPEC_FD_DIR)
+                                                             .join(descripter.as_raw_fd().to_string()))
+                                       .unwrap_or_else(|| path.clone());
+
+            libname.extend_from_slice(&address.as_os_str().as_bytes()[..]);
             libname.push(b'\0');
             let handle: *mut libc::c_void = libc::dlopen(
                 libname.as_ptr() as *const libc::c_char,
                 libc::RTLD_LAZY
             );
             if handle.eq(&ptr::null_mut()) {
-                Err(LibraryError::BadDyLib(CString::from_raw(libc::dlerror()).into_string().unwrap_or_default()))
+                Err(LibraryError::BadDyLib(dlerror()))
+            } else if let Err(why) = Library::check_abi(handle) {
+                libc::dlclose(handle);
+                Err(why)
             } else {
+                let neko_host: Option<extern fn(table: *const host::Table)> =
+                    symbol!(handle, host::SPEC_HOST_SYMBOL.as_ptr() as *const libc::c_char);
+                let name: String = path.file_stem()
+                                       .and_then(|name| name.to_str())
+                                       .unwrap_or("")
+                                       .to_string();
                 let lib: Library = Library {
                     install: symbol!(handle, b"install\0".as_ptr() as *const libc::c_char),
                     uninstall: symbol!(handle, b"uninstall\0".as_ptr() as *const libc::c_char),
@@ -96,18 +239,75 @@ impl Library {
                     input: symbol!(handle, b"input\0".as_ptr() as *const libc::c_char),
                     output: symbol!(handle, b"output\0".as_ptr() as *const libc::c_char),
                     resized: symbol!(handle, b"resized\0".as_ptr() as *const libc::c_char),
-                    save: ptr::null_mut(),
+                    on_event: symbol!(handle, event::SPEC_EVENT_SYMBOL.as_ptr() as *const libc::c_char),
+                    save: Cell::new(ptr::null_mut()),
                     handle: handle,
                     index: index,
                     path: path,
-                    unmounted: false,
+                    copy: copy,
+                    binding: Binding::new(name, capabilities, host.clone()),
+                    capabilities: capabilities,
                 };
-                lib.start(state);
+                if let Some(neko_host) = neko_host {
+                    neko_host(lib.binding.as_table());
+                }
                 Ok(lib)
             }
         }
     }
 
+    /// The function `check_abi` refuses a library which doesn't export
+    /// the `neko_abi_version` of the running Neko's layout.
+    unsafe fn check_abi(handle: *mut libc::c_void) -> Result<()> {
+        let version: *const libc::c_uint = libc::dlsym(
+            handle,
+            SPEC_ABI_SYMBOL.as_ptr() as *const libc::c_char
+        ) as *const libc::c_uint;
+
+        if version.is_null() {
+            Err(LibraryError::NoAbi)
+        } else if (*version).ne(&SPEC_ABI_VERSION) {
+            Err(LibraryError::BadAbi(*version))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The function `get_hooks` returns the names of extern functions
+    /// exported by a dynamic library without starting it.
+    /// @ path: `$HOME/.neko/lib/arukana@libnya.so`.
+    pub fn get_hooks(path: &Path) -> Result<Vec<&'static str>> {
+        unsafe {
+            let mut libname: Vec<libc::c_uchar> = Vec::with_capacity(4096);
+
+            libname.extend_from_slice(&path.as_os_str().as_bytes()[..]);
+            libname.push(b'\0');
+            let handle: *mut libc::c_void = libc::dlopen(
+                libname.as_ptr() as *const libc::c_char,
+                libc::RTLD_LAZY
+            );
+            if handle.eq(&ptr::null_mut()) {
+                Err(LibraryError::BadDyLib(dlerror()))
+            } else if let Err(why) = Library::check_abi(handle) {
+                libc::dlclose(handle);
+                Err(why)
+            } else {
+                let hooks: Vec<&'static str> = SPEC_HOOKS.iter()
+                    .chain(&[event::SPEC_EVENT_HOOK])
+                    .filter(|hook| {
+                        let symbol: CString = CString::new(**hook).unwrap_or_default();
+
+                        libc::dlsym(handle, symbol.as_ptr()).ne(&ptr::null_mut())
+                    })
+                    .cloned()
+                    .collect::<Vec<&'static str>>();
+
+                libc::dlclose(handle);
+                Ok(hooks)
+            }
+        }
+    }
+
     /// The accessor method `as_path_buf` return address of library.
     pub fn as_path_buf(&self) -> &PathBuf {
         &self.path
@@ -118,171 +318,279 @@ impl Library {
         self.index
     }
 
+    /// The method `is_unmounted` returns true if the library
+    /// requested to be unmounted by its host's table.
     pub fn is_unmounted(&self) -> bool {
-        self.unmounted
+        self.binding.is_unmounting()
+    }
+
+    pub fn get_capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    /// The method `restricted` calls a extern function with the state
+    /// and the userdata, the ungranted changes of the state are reverted:
+    /// only the lock, the persona and the tooltip which the library
+    /// isn't granted are saved before the call.
+    fn restricted<F: FnOnce(*mut LibraryState, *mut *mut libc::c_void)>(&self,
+                                                                        state: &mut LibraryState,
+                                                                        hook: F) {
+        let locked: Option<bool> = if self.capabilities.contains(Capability::Lock) {
+            None
+        } else {
+            Some(state.is_locked())
+        };
+        let persona: Option<(Persona, Tooltip)> = if self.capabilities.contains(Capability::Persona) {
+            None
+        } else {
+            Some((*state.get_persona(), *state.get_tooltip()))
+        };
+
+        hook(&mut *state, self.save.as_ptr());
+        if let Some(locked) = locked {
+            state.set_lock(locked);
+        }
+        if let Some((persona, tooltip)) = persona {
+            state.set_persona(persona);
+            state.set_tooltip(tooltip);
+        }
     }
 
     /// The method `install` call the extern function if defined
     /// when the library is installed.
-    pub fn install(&self, state: &LibraryState) {
-        if let Some(install) = self.install {
-            install(state, &self.save);
-        }
+    pub fn install(&self, state: &mut LibraryState) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::from(Kind::Install));
+        } else if let Some(install) = self.install {
+            install(state, save);
+        });
     }
 
     /// The method `uninstall` call the extern function if defined
     /// when the library is uninstalled.
-    pub fn uninstall(&self, state: &LibraryState) {
-        if let Some(uninstall) = self.uninstall {
-            uninstall(state, &self.save);
-        }
+    pub fn uninstall(&self, state: &mut LibraryState) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::from(Kind::Uninstall));
+        } else if let Some(uninstall) = self.uninstall {
+            uninstall(state, save);
+        });
     }
 
     /// The method `start` call the extern function if defined
     /// when the library is mounted.
-    pub fn start(&self, state: &LibraryState) {
-        if let Some(start) = self.start {
-            start(state, &self.save);
-        }
+    pub fn start(&self, state: &mut LibraryState) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::from(Kind::Start));
+        } else if let Some(start) = self.start {
+            start(state, save);
+        });
     }
 
     /// The method `end` call the extern function if defined
-    /// when the library is unmounted.
-    pub fn end(&self, state: &LibraryState) {
-        if let Some(end) = self.end {
-            end(state, &self.save);
-        }
+    /// when the library is unmounted, the library must free its
+    /// userdata which is forgotten after.
+    pub fn end(&self, state: &mut LibraryState) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::from(Kind::End));
+        } else if let Some(end) = self.end {
+            end(state, save);
+        });
+        self.save.set(ptr::null_mut());
     }
 
-    /// The method `end` call the extern function if defined.
-    pub fn idle(&self, state: &LibraryState) {
-        if let Some(idle) = self.idle {
-            idle(state, &self.save);
-        }
+    /// The method `idle` call the extern function if defined
+    /// when nothing happens to the terminal.
+    pub fn idle(&self, state: &mut LibraryState) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::from(Kind::Idle));
+        } else if let Some(idle) = self.idle {
+            idle(state, save);
+        });
     }
 
     /// The method `process` call the extern function if defined
     /// when the child current process as been canged.
-    pub fn process(&self, state: &LibraryState, taskname: &[libc::c_uchar], pid: libc::c_int) {
-        if let Some(process) = self.process {
-            process(state, &self.save, taskname.as_ptr(), pid);
-        }
+    pub fn process(&self, state: &mut LibraryState, taskname: &[libc::c_uchar], pid: libc::c_int) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::process(taskname, pid));
+        } else if let Some(process) = self.process {
+            process(state, save, with_nul(taskname).as_ptr(), pid);
+        });
     }
 
     /// The method `command` call the extern function if defined
     /// when a command line is outputed to the terminal.
-    pub fn command(&self, state: &LibraryState, line: &[libc::c_uchar]) {
-        if let Some(command) = self.command {
-            command(state, &self.save, line.as_ptr());
-        }
+    pub fn command(&self, state: &mut LibraryState, line: &[libc::c_uchar]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::text(Kind::Command, line));
+        } else if let Some(command) = self.command {
+            command(state, save, with_nul(line).as_ptr());
+        });
     }
 
     /// The method `key_unicode_down` call the extern function if defined
     /// when a key is pressed.
-    pub fn key_unicode_down(&self, state: &LibraryState, code: libc::c_ulonglong) {
-        if let Some(key_unicode_down) = self.key_unicode_down {
-            key_unicode_down(state, &self.save, code);
-        }
+    pub fn key_unicode_down(&self, state: &mut LibraryState, code: libc::c_ulonglong) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::key_unicode_down(code));
+        } else if let Some(key_unicode_down) = self.key_unicode_down {
+            key_unicode_down(state, save, code);
+        });
     }
 
     /// The method `key_string_down` call the extern function if defined
     /// when a text is pasted or pressed.
-    pub fn key_string_down(&self, state: &LibraryState, text: &[libc::c_uchar]) {
-        if let Some(key_string_down) = self.key_string_down {
-            key_string_down(state, &self.save, text.as_ptr());
-        }
+    pub fn key_string_down(&self, state: &mut LibraryState, text: &[libc::c_uchar]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::text(Kind::KeyStringDown, text));
+        } else if let Some(key_string_down) = self.key_string_down {
+            key_string_down(state, save, with_nul(text).as_ptr());
+        });
     }
 
     /// The method `key_repeat_down` call the extern function if defined
     /// when a key is held. It determines the time before it begins to
     /// output the held character within a given interval.
-    pub fn key_repeat_down(&self, state: &LibraryState, repeat: libc::c_ulong) {
-        if let Some(key_repeat_down) = self.key_repeat_down {
-            key_repeat_down(state, &self.save, repeat);
-        }
+    pub fn key_repeat_down(&self, state: &mut LibraryState, repeat: libc::c_ulong) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::key_repeat_down(repeat));
+        } else if let Some(key_repeat_down) = self.key_repeat_down {
+            key_repeat_down(state, save, repeat);
+        });
     }
 
     /// The method `key_interval_down` call the extern function if defined
     /// when a key is held. It determines the time between two outputs
     /// repetitions of the held character. (Triggered by key_repeat_down())
-    pub fn key_interval_down(&self, state: &LibraryState, interval: libc::c_longlong) {
-        if let Some(key_interval_down) = self.key_interval_down {
-            key_interval_down(state, &self.save, interval);
-        }
+    pub fn key_interval_down(&self, state: &mut LibraryState, interval: libc::c_longlong) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::key_interval_down(interval));
+        } else if let Some(key_interval_down) = self.key_interval_down {
+            key_interval_down(state, save, interval);
+        });
     }
 
     /// The method `mouse_pressed` call the extern function if defined
     /// when the mouse is pressed.
-    pub fn mouse_pressed(&self, state: &LibraryState, code: libc::c_uint, xy: [libc::c_ushort; 2]) {
-        if let Some(mouse_pressed) = self.mouse_pressed {
-            mouse_pressed(state, &self.save, code, xy);
-        }
+    pub fn mouse_pressed(&self, state: &mut LibraryState, code: libc::c_uint, xy: [libc::c_ushort; 2]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::mouse(Kind::MousePressed, code, xy));
+        } else if let Some(mouse_pressed) = self.mouse_pressed {
+            mouse_pressed(state, save, code, xy);
+        });
     }
 
     /// The method `mouse_released` call the extern function if defined
     /// when the mouse is released.
-    pub fn mouse_released(&self, state: &LibraryState, code: libc::c_uint, xy: [libc::c_ushort; 2]) {
-        if let Some(mouse_released) = self.mouse_released {
-            mouse_released(state, &self.save, code, xy);
-        }
+    pub fn mouse_released(&self, state: &mut LibraryState, code: libc::c_uint, xy: [libc::c_ushort; 2]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::mouse(Kind::MouseReleased, code, xy));
+        } else if let Some(mouse_released) = self.mouse_released {
+            mouse_released(state, save, code, xy);
+        });
     }
 
     /// The method `input` call the extern function if defined
     /// when something is inputted to the terminal, whatever it is.
-    pub fn input(&self, state: &LibraryState, text: &[libc::c_uchar]) {
-        if let Some(input) = self.input {
-            input(state, &self.save, text.as_ptr());
-        }
+    pub fn input(&self, state: &mut LibraryState, text: &[libc::c_uchar]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::text(Kind::Input, text));
+        } else if let Some(input) = self.input {
+            input(state, save, with_nul(text).as_ptr());
+        });
     }
 
     /// The method `output` call the extern function if defined.
     /// when something is outputted to the terminal, whatever it is.
-    pub fn output(&self, state: &LibraryState, text: &[libc::c_uchar]) {
-        if let Some(output) = self.output {
-            output(state, &self.save, text.as_ptr());
-        }
+    pub fn output(&self, state: &mut LibraryState, text: &[libc::c_uchar]) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::text(Kind::Output, text));
+        } else if let Some(output) = self.output {
+            output(state, save, with_nul(text).as_ptr());
+        });
     }
 
     /// The method `resized` call the extern function if defined
     /// when the window is resized.
-    pub fn resized(&self, state: &LibraryState, size: &pty::Winszed) {
-        if let Some(resized) = self.resized {
-            resized(state, &self.save, size);
-        }
+    pub fn resized(&self, state: &mut LibraryState, size: &pty::Winszed) {
+        self.restricted(state, |state, save| if let Some(on_event) = self.on_event {
+            on_event(state, save, &Event::resized(size));
+        } else if let Some(resized) = self.resized {
+            resized(state, save, size);
+        });
     }
 
-    /// The method `call` will read the ShellState to call an adapted extern function if defined.
-    pub fn call(&self, state: &LibraryState, event: &pty::ShellState) {
+    /// The method `call` will read the ShellState to call an adapted extern function
+    /// if defined and granted, the ungranted changes of the state are reverted.
+    pub fn call(&self, state: &mut LibraryState, event: &pty::ShellState) {
         if let Some(()) = event.is_idle() {
-            if let Some(idle) = self.idle {
-                idle(state, &self.save);
-            }
+            self.idle(state);
         } else {
+            let input: bool = self.capabilities.contains(Capability::Input);
+            let output: bool = self.capabilities.contains(Capability::Output);
+
             if let Some(pty::Mouse {code, pressed, x, y}) = event.is_input_mouse() {
-                if pressed {
-                    self.mouse_pressed(state, code as u32, [x, y])
-                } else {
-                    self.mouse_released(state, code as u32, [x, y])
+                match pressed {
+                    _ if input.not() => {},
+                    true => self.mouse_pressed(state, code as u32, [x, y]),
+                    false => self.mouse_released(state, code as u32, [x, y]),
                 }
             } else if let Some(key) = event.is_input_keydown() {
                 match key {
+                    _ if input.not() => {},
                     pty::Key::Char(code) => self.key_unicode_down(state, code),
                     pty::Key::Str(text) => self.key_string_down(state, &text.deref()),
                 }
             } else if let Some(repeat) = event.is_input_keyrepeat() {
-                self.key_repeat_down(state, repeat)
+                if input {
+                    self.key_repeat_down(state, repeat)
+                }
             } else if let Some(interval) = event.is_input_keyinterval() {
-                self.key_interval_down(state, interval)
+                if input {
+                    self.key_interval_down(state, interval)
+                }
             } else if let Some(slice) = event.is_input_slice() {
-                self.input(state, slice)
+                if input {
+                    self.input(state, slice)
+                }
             } else if let Some(slice) = event.is_output_last() {
-                self.output(state, slice)
+                if output {
+                    self.output(state, slice)
+                }
+            }
+            if self.capabilities.contains(Capability::Process) {
+                if let Some(&(pid, name)) = event.is_task() {
+                    self.process(state, &name[..], pid)
+                }
             }
-            if let Some(&(pid, name)) = event.is_task() {
-                self.process(state, &name[..], pid)
-            } 
         }
     }
+
+    /// The method `call_resized` calls the extern function `resized`,
+    /// the ungranted changes of the state are reverted.
+    pub fn call_resized(&self, state: &mut LibraryState, size: &pty::Winszed) {
+        self.resized(state, size);
+    }
+}
+
+/// The function `dlerror` returns the last error of the dynamic loader.
+unsafe fn dlerror() -> String {
+    let why: *const libc::c_char = libc::dlerror();
+
+    if why.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(why).to_string_lossy().into_owned()
+    }
+}
+
+/// The function `with_nul` returns a copy of the text ended by
+/// a null character for the hooks which receive it without length.
+fn with_nul(text: &[libc::c_uchar]) -> Vec<libc::c_uchar> {
+    let mut text: Vec<libc::c_uchar> = text.to_vec();
+
+    text.push(b'\0');
+    text
 }
 
 /// Trait for equality comparisons which are equivalence relations.
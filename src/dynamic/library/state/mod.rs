@@ -52,6 +52,18 @@ impl LibraryState {
         self.persona.get_emotion()
     }
 
+    pub fn set_lock(&mut self, locked: bool) {
+        self.lock = if locked { b'\x01' } else { b'\0' };
+    }
+
+    pub fn set_persona(&mut self, persona: Persona) {
+        self.persona = persona;
+    }
+
+    pub fn set_tooltip(&mut self, tooltip: Tooltip) {
+        self.tooltip = tooltip;
+    }
+
     pub fn set_tooltip_message(&mut self,
         message: String,
     ) {
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use ::toml;
+
+use super::err::{CompositerError, Result};
+use super::graph::Graph;
+
+/// The name of source label.
+const SPEC_LOCK_SOURCE: &'static str = "source";
+/// The name of revision label.
+const SPEC_LOCK_REVISION: &'static str = "revision";
+/// The name of commit label.
+const SPEC_LOCK_COMMIT: &'static str = "commit";
+/// The name of dependencies label.
+const SPEC_LOCK_DEPENDENCIES: &'static str = "dependencies";
+/// The name of checksum label.
+const SPEC_LOCK_CHECKSUM: &'static str = "checksum";
+/// The name of installed as dependency label.
+const SPEC_LOCK_DEPENDENCY: &'static str = "dependency";
+
+/// The struct `Locked` is the pinned state of a installed library.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Locked {
+    /// The source of repository like `https://github.com/Arukana/libnya.git`.
+    source: String,
+    /// The revision followed by update like `branch=dev`.
+    revision: String,
+    /// The resolved commit's identifiant.
+    commit: String,
+    /// The library names of dependencies like `arukana@libnya`.
+    dependencies: Vec<String>,
+    /// The checksum of the built dynamic library.
+    checksum: String,
+    /// The library is installed only as a dependency.
+    dependency: bool,
+}
+
+impl Locked {
+    /// The constructor method `new` returns a pinned library.
+    pub fn new(source: String,
+               revision: String,
+               commit: String,
+               dependencies: Vec<String>,
+               checksum: String)
+               -> Self {
+        Locked {
+            source: source,
+            revision: revision,
+            commit: commit,
+            dependencies: dependencies,
+            checksum: checksum,
+            dependency: false,
+        }
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn get_revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub fn get_commit(&self) -> &str {
+        &self.commit
+    }
+
+    pub fn get_dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    pub fn get_checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    pub fn is_dependency(&self) -> bool {
+        self.dependency
+    }
+
+    /// The mutator method `set_dependency` remembers whether the library
+    /// is installed only as a dependency.
+    pub fn set_dependency(&mut self, dependency: bool) {
+        self.dependency = dependency;
+    }
+
+    /// The constructor method `from_table` returns a pinned library
+    /// from a Neko.lock's table.
+    fn from_table(table: &toml::Table) -> Option<Self> {
+        match (table.get(SPEC_LOCK_SOURCE).and_then(|value| value.as_str()),
+               table.get(SPEC_LOCK_COMMIT).and_then(|value| value.as_str()),
+               table.get(SPEC_LOCK_CHECKSUM).and_then(|value| value.as_str())) {
+            (Some(source), Some(commit), Some(checksum)) => {
+                let mut locked: Locked = Locked::new(
+                    source.to_string(),
+                    table.get(SPEC_LOCK_REVISION)
+                         .and_then(|value| value.as_str())
+                         .unwrap_or_default()
+                         .to_string(),
+                    commit.to_string(),
+                    table.get(SPEC_LOCK_DEPENDENCIES)
+                         .and_then(|value| value.as_slice())
+                         .unwrap_or_default()
+                         .iter()
+                         .filter_map(|dep| dep.as_str())
+                         .map(|dep| dep.to_string())
+                         .collect::<Vec<String>>(),
+                    checksum.to_string(),
+                );
+
+                locked.set_dependency(table.get(SPEC_LOCK_DEPENDENCY)
+                                           .and_then(|value| value.as_bool())
+                                           .unwrap_or(false));
+                Some(locked)
+            },
+            _ => None,
+        }
+    }
+
+    /// The method `to_table` returns the Neko.lock's table of
+    /// the pinned library.
+    fn to_table(&self) -> toml::Table {
+        let mut table: toml::Table = toml::Table::new();
+
+        table.insert(SPEC_LOCK_SOURCE.to_string(),
+                     toml::Value::String(self.source.clone()));
+        table.insert(SPEC_LOCK_REVISION.to_string(),
+                     toml::Value::String(self.revision.clone()));
+        table.insert(SPEC_LOCK_COMMIT.to_string(),
+                     toml::Value::String(self.commit.clone()));
+        table.insert(SPEC_LOCK_DEPENDENCIES.to_string(),
+                     toml::Value::Array(
+                         self.dependencies.iter()
+                             .map(|dep| toml::Value::String(dep.clone()))
+                             .collect::<toml::Array>()
+                     ));
+        table.insert(SPEC_LOCK_CHECKSUM.to_string(),
+                     toml::Value::String(self.checksum.clone()));
+        if self.dependency {
+            table.insert(SPEC_LOCK_DEPENDENCY.to_string(),
+                         toml::Value::Boolean(true));
+        }
+        table
+    }
+}
+
+/// The struct `Lock` is the Neko.lock dictionary of
+/// library names and their pinned states.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Lock {
+    libraries: BTreeMap<String, Locked>,
+}
+
+impl Lock {
+    /// The constructor method `from_path` returns the Neko.lock
+    /// or a empty Lock if the file doesn't exist.
+    /// @ path: `$HOME/.neko/Neko.lock`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match File::open(path) {
+            Err(ref why) if why.kind().eq(&io::ErrorKind::NotFound) => {
+                Ok(Lock::default())
+            },
+            Err(why) => Err(CompositerError::ReadLock(why)),
+            Ok(mut descripter) => {
+                let mut buffer = String::new();
+                match descripter.read_to_string(&mut buffer) {
+                    Err(why) => Err(CompositerError::ReadLock(why)),
+                    Ok(_) => buffer.parse::<Lock>(),
+                }
+            }
+        }
+    }
+
+    /// The method `save` writes the Neko.lock.
+    /// @ path: `$HOME/.neko/Neko.lock`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        File::create(path).and_then(|mut descripter|
+            descripter.write_all(self.to_string().as_bytes())
+        ).or_else(|why: io::Error| Err(CompositerError::WriteLock(why)))
+    }
+
+    /// The accessor method `get` returns the pinned state of a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn get(&self, libraryname: &str) -> Option<&Locked> {
+        self.libraries.get(libraryname)
+    }
+
+    /// The method `insert` pins or repins a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn insert(&mut self, libraryname: String, locked: Locked) {
+        self.libraries.insert(libraryname, locked);
+    }
+
+    /// The accessor method `get_graph` returns the graph of dependencies
+    /// between the pinned libraries.
+    pub fn get_graph(&self) -> Graph {
+        let mut graph: Graph = Graph::default();
+
+        for (libraryname, locked) in &self.libraries {
+            graph.insert(libraryname.clone(), locked.dependencies.clone());
+        }
+        graph
+    }
+
+    /// The method `remove` unpins a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn remove(&mut self, libraryname: &str) -> Option<Locked> {
+        self.libraries.remove(libraryname)
+    }
+}
+
+impl FromStr for Lock {
+    type Err = CompositerError;
+
+    fn from_str(buffer: &str) -> Result<Self> {
+        if let Some(table) = toml::Parser::new(buffer).parse() {
+            table.iter()
+                 .map(|(libraryname, value)|
+                     value.as_table()
+                          .and_then(|table| Locked::from_table(table))
+                          .and_then(|locked| Some((libraryname.clone(), locked)))
+                          .ok_or(CompositerError::ParseLock))
+                 .collect::<Result<BTreeMap<String, Locked>>>()
+                 .and_then(|libraries| Ok(Lock { libraries: libraries }))
+        } else {
+            Err(CompositerError::ParseLock)
+        }
+    }
+}
+
+impl ToString for Lock {
+    fn to_string(&self) -> String {
+        toml::Value::Table(
+            self.libraries.iter()
+                .map(|(libraryname, locked)|
+                    (libraryname.clone(), toml::Value::Table(locked.to_table())))
+                .collect::<toml::Table>()
+        ).to_string()
+    }
+}
+
+impl<'a> IntoIterator for &'a Lock {
+    type Item = (&'a String, &'a Locked);
+    type IntoIter = ::std::collections::btree_map::Iter<'a, String, Locked>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.libraries.iter()
+    }
+}
@@ -0,0 +1,262 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use ::semver::{Version, VersionReq};
+use ::toml;
+
+use super::build::Build;
+use super::capability::Capabilities;
+use super::err::{CompositerError, Result};
+use super::version::parse_version;
+use super::{SPEC_PRIORITY, SPEC_PRIORITY_NAME};
+
+/// The name of name label.
+const SPEC_MANIFEST_NAME: &'static str = "name";
+/// The name of version label.
+const SPEC_MANIFEST_VERSION: &'static str = "version";
+/// The name of authors label.
+const SPEC_MANIFEST_AUTHORS: &'static str = "authors";
+/// The name of description label.
+const SPEC_MANIFEST_DESCRIPTION: &'static str = "description";
+/// The name of the minimum Neko version label.
+const SPEC_MANIFEST_NEKO: &'static str = "neko";
+/// The name of the requested capabilities label.
+const SPEC_MANIFEST_CAPABILITIES: &'static str = "capabilities";
+/// The name of dependencies section.
+const SPEC_MANIFEST_DEPENDENCIES: &'static str = "dependencies";
+/// The name of a dependency's repository label.
+const SPEC_MANIFEST_GIT: &'static str = "git";
+
+/// The struct `Dependency` is a library required by a manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dependency {
+    /// The source like `https://github.com/Arukana/libnya.git`.
+    git: String,
+    /// The version requirement like `^0.3`.
+    version: Option<String>,
+}
+
+impl Dependency {
+    /// The constructor method `new` returns a required library.
+    pub fn new(git: String, version: Option<String>) -> Self {
+        Dependency {
+            git: git,
+            version: version,
+        }
+    }
+
+    pub fn get_git(&self) -> &str {
+        &self.git
+    }
+
+    pub fn get_version(&self) -> Option<&str> {
+        self.version.as_ref().map(|version| version.as_str())
+    }
+}
+
+/// The struct `Manifest` is the validated Neko.toml of a library.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+    /// The name like `libnya`.
+    name: Option<String>,
+    /// The version like `0.3.1`.
+    version: Option<Version>,
+    /// The authors like `adjivas <adjivas@users.noreply.github.com>`.
+    authors: Vec<String>,
+    /// The description of the library.
+    description: Option<String>,
+    /// The priority of the library.
+    priority: i64,
+    /// The `[build]` section.
+    build: Build,
+    /// The `[dependencies]` section by name.
+    dependencies: Vec<(String, Dependency)>,
+    /// The minimum version of Neko like `0.1.0`.
+    neko: Option<Version>,
+    /// The capabilities requested like `["input", "persona"]`.
+    capabilities: Capabilities,
+}
+
+impl Manifest {
+    /// The constructor method `from_path` returns the manifest
+    /// of a repository.
+    /// @ path: `$HOME/.neko/git/arukana@libnya/Neko.toml`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match File::open(path) {
+            Err(why) => Err(CompositerError::OpenDirLib(why)),
+            Ok(mut descripter) => {
+                let mut buffer = String::new();
+                match descripter.read_to_string(&mut buffer) {
+                    Err(why) => Err(CompositerError::ReadManifest(why)),
+                    Ok(_) => buffer.parse::<Manifest>(),
+                }
+            }
+        }
+    }
+
+    /// The constructor method `from_table` returns the manifest
+    /// or the first key with a unvalid value.
+    pub fn from_table(table: &toml::Table) -> Result<Self> {
+        let mut dependencies: Vec<(String, Dependency)> = Vec::new();
+
+        if let Some(value) = table.get(SPEC_MANIFEST_DEPENDENCIES) {
+            for (name, dependency) in try!(value.as_table().ok_or_else(||
+                unvalid(SPEC_MANIFEST_DEPENDENCIES, value))) {
+                let key: String = format!("{}.{}", SPEC_MANIFEST_DEPENDENCIES, name);
+                let dependency: &toml::Table = try!(dependency.as_table().ok_or_else(||
+                    unvalid(&key, dependency)));
+                let git: String = try!(try!(get_str(dependency, SPEC_MANIFEST_GIT, &key))
+                    .ok_or_else(|| CompositerError::ManifestKey(
+                        format!("{}.{}", key, SPEC_MANIFEST_GIT), String::new()
+                    )));
+                let version: Option<String> =
+                    try!(get_str(dependency, SPEC_MANIFEST_VERSION, &key));
+
+                if let Some(ref version) = version {
+                    if VersionReq::parse(version).is_err() {
+                        return Err(CompositerError::ManifestKey(
+                            format!("{}.{}", key, SPEC_MANIFEST_VERSION),
+                            version.clone()
+                        ));
+                    }
+                }
+                dependencies.push((name.clone(), Dependency::new(git, version)));
+            }
+        }
+        Ok(Manifest {
+            name: try!(get_str(table, SPEC_MANIFEST_NAME, "")),
+            version: try!(get_version(table, SPEC_MANIFEST_VERSION)),
+            authors: match table.get(SPEC_MANIFEST_AUTHORS) {
+                None => Vec::new(),
+                Some(value) => {
+                    try!(value.as_slice()
+                              .and_then(|authors| authors.iter()
+                                                         .map(|author| author.as_str()
+                                                                             .map(|author| author.to_string()))
+                                                         .collect::<Option<Vec<String>>>())
+                              .ok_or_else(|| unvalid(SPEC_MANIFEST_AUTHORS, value)))
+                },
+            },
+            description: try!(get_str(table, SPEC_MANIFEST_DESCRIPTION, "")),
+            priority: match table.get(SPEC_PRIORITY_NAME) {
+                None => SPEC_PRIORITY,
+                Some(value) => {
+                    try!(value.as_integer()
+                              .ok_or_else(|| unvalid(SPEC_PRIORITY_NAME, value)))
+                },
+            },
+            build: try!(Build::from_manifest(table)),
+            dependencies: dependencies,
+            neko: try!(get_version(table, SPEC_MANIFEST_NEKO)),
+            capabilities: match table.get(SPEC_MANIFEST_CAPABILITIES) {
+                None => Capabilities::default(),
+                Some(value) => {
+                    try!(value.as_slice()
+                              .ok_or_else(|| unvalid(SPEC_MANIFEST_CAPABILITIES, value))
+                              .and_then(Capabilities::from_slice))
+                },
+            },
+        })
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn get_version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+
+    pub fn get_authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_ref().map(|description| description.as_str())
+    }
+
+    pub fn get_priority(&self) -> i64 {
+        self.priority
+    }
+
+    pub fn get_build(&self) -> &Build {
+        &self.build
+    }
+
+    pub fn get_dependencies(&self) -> &[(String, Dependency)] {
+        &self.dependencies
+    }
+
+    pub fn get_neko(&self) -> Option<&Version> {
+        self.neko.as_ref()
+    }
+
+    pub fn get_capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    /// The method `is_supported` returns true if the running Neko
+    /// isn't older than the minimum version of the manifest.
+    /// @ neko: `0.1.0`.
+    pub fn is_supported(&self, neko: &Version) -> bool {
+        self.neko.as_ref().map_or(true, |minimum| neko.ge(minimum))
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = CompositerError;
+
+    fn from_str(buffer: &str) -> Result<Self> {
+        let mut parser: toml::Parser = toml::Parser::new(buffer);
+
+        match parser.parse() {
+            Some(table) => Manifest::from_table(&table),
+            None => Err(CompositerError::ParseManifest(
+                parser.errors.iter()
+                             .map(|error| {
+                                 let (line, column) = parser.to_linecol(error.lo);
+                                 format!("{}:{} {}", line + 1, column + 1, error.desc)
+                             })
+                             .collect::<Vec<String>>()
+                             .join(", ")
+            )),
+        }
+    }
+}
+
+/// The function `unvalid` returns the error of a key with a unvalid value.
+fn unvalid(key: &str, value: &toml::Value) -> CompositerError {
+    CompositerError::ManifestKey(key.to_string(), value.to_string())
+}
+
+/// The function `get_str` returns the string of a optional key.
+/// @ prefix: `dependencies.libnya`.
+fn get_str(table: &toml::Table, key: &str, prefix: &str) -> Result<Option<String>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => {
+            value.as_str()
+                 .map(|value| Some(value.to_string()))
+                 .ok_or_else(|| if prefix.is_empty() {
+                     unvalid(key, value)
+                 } else {
+                     unvalid(&format!("{}.{}", prefix, key), value)
+                 })
+        },
+    }
+}
+
+/// The function `get_version` returns the version of a optional key.
+fn get_version(table: &toml::Table, key: &str) -> Result<Option<Version>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => {
+            value.as_str()
+                 .and_then(|version| parse_version(version).ok())
+                 .map(Some)
+                 .ok_or_else(|| unvalid(key, value))
+        },
+    }
+}
@@ -1,4 +1,19 @@
 pub mod library;
+pub mod build;
+pub mod bundle;
+pub mod capability;
+pub mod graph;
+pub mod lock;
+pub mod manifest;
+pub mod registry;
+pub mod mount;
+pub mod progress;
+pub mod revision;
+pub mod source;
+pub mod swap;
+pub mod trust;
+pub mod version;
+pub mod watch;
 mod err;
 
 use std::env;
@@ -6,23 +21,41 @@ use std::ffi::OsStr;
 use std::ops::Not;
 use std::fs;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
+use std::os::unix;
 use std::path::{Path, PathBuf};
-use std::process;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
 
 
 pub use self::err::{CompositerError, Result};
 use self::library::LibraryState;
 use self::library::Library;
+use self::library::header;
+use self::library::host::Host;
 use self::library::state::Relative;
 use self::library::state::persona::Position;
+use self::build::{Build, Backend};
+use self::capability::Capabilities;
+use self::graph::Graph;
+use self::lock::{Lock, Locked};
+use self::manifest::Manifest;
+use self::mount::{Mount, Mounted};
+use self::progress::{Progress, Reporter};
+use self::registry::{Registry, Entry};
+use self::revision::Revision;
+use self::source::Source;
+use self::trust::Trust;
+use self::version::{Requirements, parse_version};
+use self::watch::Watch;
 
 use ::SPEC_ROOT;
 
 use ::graphic;
 use ::pty;
-use ::toml;
 use ::git2;
+use ::semver;
 use ::pty_proc::shell::ShellState;
 
 /// The default capacity of heap.
@@ -31,7 +64,14 @@ const SPEC_CAPACITY: usize = 10;
 pub const SPEC_ROOT_DEFAULT: &'static str = ".neko";
 
 /// The shared library extention.
+#[cfg(target_os = "macos")]
 const SPEC_LIB_EXT: &'static str = "dylib";
+/// The shared library extention.
+#[cfg(not(target_os = "macos"))]
+const SPEC_LIB_EXT: &'static str = "so";
+/// The shared library extention of the libraries installed
+/// before the platform's extention.
+const SPEC_LIB_EXT_LEGACY: &'static str = "dylib";
 
 /// The default priority of call.
 const SPEC_PRIORITY: i64 = 0i64;
@@ -41,47 +81,125 @@ const SPEC_PRIORITY_NAME: &'static str = "priority";
 const SPEC_SUBD_GIT: &'static str = "git";
 /// The sub-directory lib.
 const SPEC_SUBD_LIB: &'static str = "lib";
+/// The sub-directory of the previous versions.
+const SPEC_SUBD_ROLLBACK: &'static str = "rollback";
+/// The private sub-directory of the copies of reloaded libraries.
+const SPEC_SUBD_COPY: &'static str = "copy";
+/// The sub-directory of a staged update.
+const SPEC_SUBD_STAGING: &'static str = "staging";
+/// The sub-directory of the build logs.
+const SPEC_SUBD_LOG: &'static str = "log";
+/// The extention of a build log.
+const SPEC_LOG_EXT: &'static str = "log";
+/// The sub-directory of the C header of the libraries.
+const SPEC_SUBD_INCLUDE: &'static str = "include";
+/// The variable of the include directory given to a build.
+const SPEC_INCLUDE: &'static str = "NEKO_INCLUDE";
+/// The log of the libraries.
+const SPEC_HOST_LOG: &'static str = "neko.log";
+/// The sub-directory where a bundle is unpacked.
+const SPEC_SUBD_BUNDLE: &'static str = "bundle";
+/// The extention of a bundle.
+const SPEC_BUNDLE_EXT: &'static str = "tar";
+/// The sub-directory registry.
+const SPEC_SUBD_REGISTRY: &'static str = "registry";
+/// The environement variable of the registry's repository.
+const SPEC_REGISTRY: &'static str = "NEKO_REGISTRY";
 /// The manigest NEKO file.
 const SPEC_MANIFEST: &'static str = "Neko.toml";
+/// The lock NEKO file.
+const SPEC_LOCK: &'static str = "Neko.lock";
+/// The mount NEKO file.
+const SPEC_MOUNT: &'static str = "Neko.mount";
+/// The trust NEKO file.
+const SPEC_TRUST: &'static str = "Neko.trust";
+/// The git configuration key of the followed revision.
+const SPEC_REVISION_CONFIG: &'static str = "neko.revision";
 
 /// The struct `Compositer` is a heap of a double tuple
 /// of a dynamic libraries and a priority order.
 #[derive(Debug)]
 pub struct Compositer {
+    /// The Neko root directory.
+    root: PathBuf,
     state: LibraryState,
     list: Vec<Library>,
+    /// inotify's watcher of the lib sub-directory.
+    watch: Option<Watch>,
+    /// The progress of the installation which runs in background.
+    installing: Option<mpsc::Receiver<Progress>>,
+    /// The capabilities approved for the installation in background.
+    approved: Capabilities,
+    /// The account to confirm, the repository and the capabilities
+    /// of a installation in background refused because it's untrusted.
+    untrusted: Option<(String, String, Capabilities)>,
+    /// The libraries of the Neko.mount mounted before the capabilities,
+    /// which the user is asked once to grant with `migrate`.
+    legacy: Vec<String>,
+    /// The reporter of a Compositer which installs in background,
+    /// its libraries are recorded to the Neko.mount without being loaded.
+    progress: Option<Reporter>,
+    /// The host's functions shared by the libraries.
+    host: Rc<Host>,
 }
 
 impl Compositer {
     /// The constructor `new` returns a Compositer prepared with
-    /// the library root.
+    /// the library root from the $NEKO_PATH variable, the libraries
+    /// are mounted with the priorities of the Neko.mount unless unmounted.
     pub fn new() -> Result<Self> {
+        Compositer::with_root(Compositer::default().get_root())
+    }
+
+    /// The constructor `with_root` returns a Compositer prepared with
+    /// a library root and its neko.h, the libraries are mounted with
+    /// the priorities of the Neko.mount unless unmounted.
+    /// @ root: `$HOME/.neko`.
+    pub fn with_root<P: AsRef<Path>>(root: P) -> Result<Self> {
         let mut compositer: Compositer = Compositer::default();
 
-        compositer.get_lib().and_then(|lib| match fs::read_dir(lib) {
+        compositer.root = root.as_ref().to_path_buf();
+        try!(compositer.header());
+        compositer.host = Rc::new(Host::new(
+            Some(try!(compositer.get_log()).join(SPEC_HOST_LOG))
+        ));
+        compositer.get_lib().and_then(|lib| match fs::read_dir(&lib) {
             Err(why) => Err(CompositerError::ReadDirGit(why)),
             Ok(entries) => {
                 entries.filter_map(|library| library.ok()).all(|entry| {
-                    compositer.mount(
-                            &entry.path().file_stem().unwrap_or_default(),
-                            None
+                    compositer.remount(
+                            &entry.path().file_stem().unwrap_or_default()
                         ).is_ok()
                 });
+                compositer.watch = Watch::new(lib).ok();
+                compositer.legacy = compositer.get_mount()
+                    .map(|mount| mount.into_iter()
+                                      .filter(|&(_, mounted)| mounted.get_capabilities().is_none())
+                                      .map(|(libraryname, _)| libraryname.clone())
+                                      .collect::<Vec<String>>())
+                    .unwrap_or_default();
+                if compositer.legacy.is_empty().not() {
+                    let question: String = format!(
+                        "{} mounted before the capabilities, grant the capabilities \
+                         requested by their manifest? [y/N]",
+                        compositer.legacy.join(", ")
+                    );
+
+                    compositer.state.set_tooltip_message(question);
+                }
                 Ok(compositer)
             }
         })
     }
 
+    /// The accessor method `get_root` returns the Neko root directory.
+    pub fn get_root(&self) -> PathBuf {
+        self.root.clone()
+    }
+
     /// The accessor method `get_git` returns the git sub-directory.
     pub fn get_git(&self) -> Result<PathBuf> {
-        let path: PathBuf =
-            env::var(SPEC_ROOT).ok()
-                .and_then(|repertory: String|
-                          Some(PathBuf::from(repertory)))
-                .unwrap_or_else(||
-                          PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                                            .join(SPEC_ROOT_DEFAULT))
-	        	.join(SPEC_SUBD_GIT);
+        let path: PathBuf = self.get_root().join(SPEC_SUBD_GIT);
         match fs::create_dir_all(&path) {
             Ok(_) => Ok(path),
             Err(why) => {
@@ -91,19 +209,12 @@ impl Compositer {
                     Err(CompositerError::MkDirGit(why))
                 }
             },
-	    }
+        }
     }
 
     /// The accessor method `get_lib` returns the lib sub-directory.
     pub fn get_lib(&self) -> Result<PathBuf> {
-        let path: PathBuf =
-            env::var(SPEC_ROOT).ok()
-                .and_then(|repertory: String|
-                          Some(PathBuf::from(repertory)))
-                .unwrap_or_else(||
-                          PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                                            .join(SPEC_ROOT_DEFAULT))
-		.join(SPEC_SUBD_LIB);
+        let path: PathBuf = self.get_root().join(SPEC_SUBD_LIB);
         match fs::create_dir_all(&path) {
             Ok(_) => Ok(path),
             Err(why) => {
@@ -113,272 +224,1975 @@ impl Compositer {
                     Err(CompositerError::MkDirLib(why))
                 }
             },
-	    }
+        }
+    }
+
+    /// The accessor method `get_rollback` returns the rollback sub-directory
+    /// where a update is staged and where the previous version is kept.
+    pub fn get_rollback(&self) -> Result<PathBuf> {
+        let path: PathBuf = self.get_root().join(SPEC_SUBD_ROLLBACK);
+        match fs::create_dir_all(&path) {
+            Ok(_) => Ok(path),
+            Err(why) => {
+                if why.kind().eq(&io::ErrorKind::AlreadyExists) {
+                    Ok(path)
+                } else {
+                    Err(CompositerError::MkDirRollback(why))
+                }
+            },
+        }
+    }
+
+    /// The accessor method `get_log` returns the log sub-directory
+    /// where the output of the builds is kept.
+    pub fn get_log(&self) -> Result<PathBuf> {
+        let path: PathBuf = self.get_root().join(SPEC_SUBD_LOG);
+        match fs::create_dir_all(&path) {
+            Ok(_) => Ok(path),
+            Err(why) => {
+                if why.kind().eq(&io::ErrorKind::AlreadyExists) {
+                    Ok(path)
+                } else {
+                    Err(CompositerError::MkDirLog(why))
+                }
+            },
+        }
+    }
+
+    /// The accessor method `get_include` returns the include sub-directory
+    /// where the C header of the libraries is kept.
+    pub fn get_include(&self) -> Result<PathBuf> {
+        let path: PathBuf = self.get_root().join(SPEC_SUBD_INCLUDE);
+        match fs::create_dir_all(&path) {
+            Ok(_) => Ok(path),
+            Err(why) => {
+                if why.kind().eq(&io::ErrorKind::AlreadyExists) {
+                    Ok(path)
+                } else {
+                    Err(CompositerError::MkDirInclude(why))
+                }
+            },
+        }
+    }
+
+    /// The method `header` writes the neko.h of the running Neko
+    /// to the include sub-directory and returns this directory.
+    pub fn header(&self) -> Result<PathBuf> {
+        self.get_include().and_then(|include|
+            File::create(include.join(header::SPEC_HEADER))
+                 .and_then(|mut descripter| descripter.write_all(header::generate().as_bytes()))
+                 .map(|_| include)
+                 .map_err(CompositerError::WriteHeader)
+        )
+    }
+
+    /// The accessor method `get_lock` returns the Neko.lock dictionary,
+    /// with the pins sent to the main thread by a Compositer
+    /// which installs in background.
+    pub fn get_lock(&self) -> Result<Lock> {
+        let mut lock: Lock = try!(Lock::from_path(&self.get_root().join(SPEC_LOCK)));
+
+        if let Some(ref progress) = self.progress {
+            progress.apply_lock(&mut lock);
+        }
+        Ok(lock)
+    }
+
+    /// The accessor method `get_mount` returns the Neko.mount dictionary,
+    /// with the mount states sent to the main thread by a Compositer
+    /// which installs in background.
+    pub fn get_mount(&self) -> Result<Mount> {
+        let mut mount: Mount = try!(Mount::from_path(&self.get_root().join(SPEC_MOUNT)));
+
+        if let Some(ref progress) = self.progress {
+            progress.apply_mount(&mut mount);
+        }
+        Ok(mount)
+    }
+
+    /// The accessor method `get_trust` returns the Neko.trust policy,
+    /// with the accounts sent to the main thread by a Compositer
+    /// which installs in background.
+    pub fn get_trust(&self) -> Result<Trust> {
+        let mut trust: Trust = try!(Trust::from_path(&self.get_root().join(SPEC_TRUST)));
+
+        if let Some(ref progress) = self.progress {
+            progress.apply_trust(&mut trust);
+        }
+        Ok(trust)
+    }
+
+    /// The method `trust` confirms a account on its host to the Neko.trust,
+    /// its repositories can be built. A Compositer which installs
+    /// in background sends it to the main thread.
+    /// @ account: `github.com/arukana`.
+    pub fn trust(&self, account: &str) -> Result<()> {
+        let mut trust: Trust = try!(self.get_trust());
+
+        if trust::is_account(account).not() {
+            return Err(CompositerError::TrustAccount(account.to_string()));
+        }
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Trust(account.to_string()));
+            return Ok(());
+        }
+        trust.insert_account(account.to_lowercase());
+        trust.save(&self.get_root().join(SPEC_TRUST))
+    }
+
+    /// The method `trust_fingerprint` confirms a key to the Neko.trust,
+    /// the commits signed by it are verified. A Compositer which installs
+    /// in background sends it to the main thread.
+    /// @ fingerprint: `D8692123C4065DEA5E0F3AB5249B39D24F25E3B6`.
+    pub fn trust_fingerprint(&self, fingerprint: &str) -> Result<()> {
+        let mut trust: Trust = try!(self.get_trust());
+
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Fingerprint(fingerprint.to_string()));
+            return Ok(());
+        }
+
+        trust.insert_fingerprint(fingerprint);
+        trust.save(&self.get_root().join(SPEC_TRUST))
+    }
+
+    /// The method `trust_host` confirms a host to the Neko.trust,
+    /// every repository from it can be built. A Compositer which installs
+    /// in background sends it to the main thread.
+    /// @ host: `github.com`.
+    pub fn trust_host(&self, host: &str) -> Result<()> {
+        let mut trust: Trust = try!(self.get_trust());
+
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Host(host.to_string()));
+            return Ok(());
+        }
+
+        trust.insert_host(host.to_lowercase());
+        trust.save(&self.get_root().join(SPEC_TRUST))
+    }
+
+    /// The method `get_origin` returns the remote url and
+    /// the commit of the head of a repository.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    fn get_origin(&self, source: &PathBuf) -> Result<(String, String)> {
+        let repo: git2::Repository = try!(git2::Repository::open(source)
+            .map_err(CompositerError::UpdateRepOpen));
+        let url: String = repo.find_remote("origin")
+                              .ok()
+                              .and_then(|remote| remote.url().map(|url| url.to_string()))
+                              .unwrap_or_else(|| source.display().to_string());
+        let commit: String = try!(repo.head().map_err(CompositerError::LockRepHead))
+                                 .target()
+                                 .map(|id| id.to_string())
+                                 .unwrap_or_default();
+
+        Ok((url, commit))
+    }
+
+    /// The method `get_account` returns the account of a library
+    /// on the host of its remote url like `github.com/arukana`.
+    /// @ url: `https://github.com/Arukana/libnya.git`.
+    /// @ sub: `arukana@libnya`.
+    fn get_account<S: AsRef<OsStr>>(&self, url: &str, sub: &S) -> (String, Option<String>) {
+        (sub.as_ref().to_str()
+            .and_then(|sub| sub.split('@').next())
+            .unwrap_or_default()
+            .to_string(),
+         trust::get_host(url))
+    }
+
+    /// The method `untrusted` returns the confirmation to ask for
+    /// a repository which isn't trusted.
+    /// @ url: `https://github.com/Arukana/libnya.git`.
+    /// @ sub: `arukana@libnya`.
+    fn untrusted<S: AsRef<OsStr>>(&self, url: String, commit: String, sub: &S) -> CompositerError {
+        let (account, host): (String, Option<String>) = self.get_account(&url, sub);
+
+        CompositerError::Untrusted(trust::get_account(&account, host.as_ref().map(|host| host.as_str())),
+                                   url,
+                                   commit)
+    }
+
+    /// The method `is_trusted` returns true if the account of a library
+    /// on the host of its remote url, or this host, is trusted.
+    /// @ url: `https://github.com/Arukana/libnya.git`.
+    /// @ sub: `arukana@libnya`.
+    fn is_trusted<S: AsRef<OsStr>>(&self, trust: &Trust, url: &str, sub: &S) -> bool {
+        let (account, host): (String, Option<String>) = self.get_account(url, sub);
+
+        trust.is_trusted(&account, host.as_ref().map(|host| host.as_str()))
+    }
+
+    /// The method `verify` refuses to build a repository which isn't
+    /// trusted by the Neko.trust, or whose commit isn't signed when
+    /// the signatures are required. A local directory is trusted.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    /// @ sub: `arukana@libnya`.
+    fn verify<S: AsRef<OsStr>>(&self, source: &PathBuf, sub: &S) -> Result<()> {
+        if self.is_linked(source) {
+            return Ok(());
+        }
+        let trust: Trust = try!(self.get_trust());
+        let (url, commit): (String, String) = try!(self.get_origin(source));
+
+        if self.is_trusted(&trust, &url, sub).not() {
+            Err(self.untrusted(url, commit, sub))
+        } else if trust.is_signed() {
+            self.verify_signature(&trust, source, &commit)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The method `verify_signature` refuses a commit which isn't signed
+    /// or whose signature isn't verified by gpg with a key of the Neko.trust.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    /// @ commit: `4e5d3c...`.
+    fn verify_signature(&self, trust: &Trust, source: &PathBuf, commit: &str) -> Result<()> {
+        let repo: git2::Repository = try!(git2::Repository::open(source)
+            .map_err(CompositerError::UpdateRepOpen));
+        let id: git2::Oid = try!(git2::Oid::from_str(commit)
+            .map_err(CompositerError::LockRepCommit));
+
+        match repo.extract_signature(&id, None) {
+            Err(_) => Err(CompositerError::Unsigned(commit.to_string())),
+            Ok((signature, data)) => {
+                trust::verify_signature(trust, &self.get_root(), &signature, &data).and_then(|verified|
+                    if verified {
+                        Ok(())
+                    } else {
+                        Err(CompositerError::BadSignature(commit.to_string()))
+                    })
+            },
+        }
+    }
+
+    /// The method `trust_dependency` verifies a dependency like `verify`,
+    /// a untrusted dependency is refused with its repository and its commit
+    /// to confirm, unless the Neko.trust trusts the dependencies on first use.
+    /// @ source: `$HOME/.neko/git/arukana@libmeow`.
+    /// @ sub: `arukana@libmeow`.
+    fn trust_dependency(&self, source: &PathBuf, sub: &str) -> Result<()> {
+        if self.is_linked(source) {
+            return Ok(());
+        }
+        let mut trust: Trust = try!(self.get_trust());
+        let (url, commit): (String, String) = try!(self.get_origin(source));
+
+        if self.is_trusted(&trust, &url, &sub).not() {
+            match self.untrusted(url, commit.clone(), &sub) {
+                CompositerError::Untrusted(ref account, _, _) if trust.is_refusing().not() => {
+                    try!(self.trust(account));
+                    trust.insert_account(account.to_lowercase());
+                },
+                why => return Err(why),
+            }
+        }
+        if trust.is_signed() {
+            self.verify_signature(&trust, source, &commit)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The method `set_mounted` records the mount state of
+    /// a library to the Neko.mount, or forgets it. A Compositer
+    /// which installs in background sends it to the main thread.
+    /// @ libraryname: `arukana@libnya`.
+    fn set_mounted<S: AsRef<OsStr>>(&self,
+                                    libraryname: &S,
+                                    mounted: Option<Mounted>)
+                                    -> Result<()> {
+        let path: PathBuf = self.get_root().join(SPEC_MOUNT);
+        let libraryname: &str = libraryname.as_ref().to_str().unwrap_or_default();
+
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Mount(libraryname.to_string(), mounted));
+            return Ok(());
+        }
+        let mut mount: Mount = try!(Mount::from_path(&path));
+
+        if let Some(mounted) = mounted {
+            mount.insert(libraryname.to_string(), mounted);
+        } else {
+            mount.remove(libraryname);
+        }
+        mount.save(&path)
+    }
+
+    /// The method `set_ungranted` records a newly installed library
+    /// without granted capability, unlike the libraries installed
+    /// before the capabilities which keep their full access.
+    /// A library already installed or recorded to the Neko.mount
+    /// keeps its mount state.
+    /// @ libraryname: `arukana@libmeow`.
+    fn set_ungranted<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<()> {
+        let name: &str = libraryname.as_ref().to_str().unwrap_or_default();
+
+        if fs::symlink_metadata(try!(self.get_git()).join(name)).is_ok() ||
+           try!(self.get_mount()).get(name).is_some() {
+            return Ok(());
+        }
+        let mut mounted: Mounted = Mounted::new(true, None);
+
+        mounted.set_capabilities(Capabilities::default());
+        self.set_mounted(libraryname, Some(mounted))
+    }
+
+    /// The accessor method `get_registry` clones or refreshes the registry
+    /// to the Neko root and returns its entries, the repository is a url
+    /// or a local bare repository from the $NEKO_REGISTRY variable.
+    pub fn get_registry(&self) -> Result<Registry> {
+        let url: String = try!(env::var(SPEC_REGISTRY)
+            .or(Err(CompositerError::RegistryUnset)));
+        let dest: PathBuf = self.get_root().join(SPEC_SUBD_REGISTRY);
+
+        if dest.exists() {
+            let repo: git2::Repository = try!(git2::Repository::open(&dest)
+                .map_err(CompositerError::UpdateRepOpen));
+
+            try!(self.fetch(&repo));
+            let object: git2::Object = try!(self.resolve(&repo, &Revision::Head));
+
+            try!(self.reset(&repo, &object));
+        } else if let Err(why) = git2::Repository::clone(&url, &dest) {
+            return Err(CompositerError::RegistryClone(why));
+        }
+        Registry::from_path(&dest)
+    }
+
+    /// The method `search` returns a line by library of the registry
+    /// whose the name or the description contains the term.
+    /// @ term: `nya`.
+    pub fn search(&self, term: &str) -> Result<String> {
+        self.get_registry().and_then(|registry| {
+            let entries: Vec<&Entry> = registry.search(term);
+
+            if entries.is_empty() {
+                Ok(format!("No library matches \"{}\".", term))
+            } else {
+                Ok(entries.iter()
+                          .map(|entry| format!("{} [{}] {}",
+                                               entry.get_name(),
+                                               entry.get_versions().join(", "),
+                                               entry.get_description()))
+                          .collect::<Vec<String>>()
+                          .join("\n"))
+            }
+        })
+    }
+
+    /// The method `get_source` returns the source of a repository,
+    /// a short name like `libnya#v0.3.1` is resolved by the registry.
+    /// @ repo: `https://github.com/Arukana/libnya.git` or `libnya`.
+    fn get_source(&self, repo: &str) -> Result<Source> {
+        match try!(repo.parse::<Source>()) {
+            Source::Git(ref name, ref revision) if name.contains('/').not() &&
+                                                   name.contains(':').not() => {
+                self.get_registry().and_then(|registry|
+                    registry.get(name)
+                            .map(|entry| Source::Git(entry.get_url().to_string(),
+                                                     revision.clone()))
+                            .ok_or_else(|| CompositerError::RegistryName(name.clone())))
+            },
+            source => Ok(source),
+        }
     }
 
     /// The accessor method `git_with_lib` returns a couple
     /// of `git` and `lib` sub-repositories.
-    pub fn git_with_lib(&mut self) -> Result<(PathBuf, PathBuf)> {
+    pub fn git_with_lib(&self) -> Result<(PathBuf, PathBuf)> {
         match (self.get_git(), self.get_lib()) {
             (Err(why), _) | (_, Err(why)) => Err(why),
             (Ok(git), Ok(lib)) => Ok((git, lib)),
         }
     }
 
-    /// The accessor method `get_manifest` returns a dictionary.
+    /// The accessor method `get_manifest` returns the validated manifest.
     /// @repository: `$HOME/.neko/git/arukana@libnya`.
-    pub fn get_manifest(&self, repository: &PathBuf) -> Result<toml::Table> {
-        match File::open(repository.join(SPEC_MANIFEST)) {
-            Err(why) => Err(CompositerError::OpenDirLib(why)),
-            Ok(mut descripter) => {
-                let mut buffer = String::new();
-                match descripter.read_to_string(&mut buffer) {
-                    Err(why) => Err(CompositerError::ReadManifest(why)),
-                    Ok(_) => {
-                        if let Some(table) = toml::Parser::new(&buffer)
-                            .parse() {
-                            Ok(table)
-                        } else {
-                            Err(CompositerError::ParseManifest)
-                        }
-                    }
-                }
-            }
+    pub fn get_manifest(&self, repository: &PathBuf) -> Result<Manifest> {
+        Manifest::from_path(&repository.join(SPEC_MANIFEST))
+    }
+
+    /// The method `find_library` returns the dynamic library of
+    /// a installed library with the platform's extention, or else
+    /// with the legacy extention when it's the only one installed.
+    /// @ lib: `$HOME/.neko/lib`.
+    /// @ libraryname: `arukana@libnya`.
+    fn find_library<S: AsRef<OsStr>>(&self,
+                                     lib: &PathBuf,
+                                     libraryname: &S)
+                                     -> PathBuf {
+        let library: PathBuf = lib.join(libraryname.as_ref())
+                                  .with_extension(SPEC_LIB_EXT);
+        let legacy: PathBuf = library.with_extension(SPEC_LIB_EXT_LEGACY);
+
+        if library.exists().not() && legacy.exists() {
+            legacy
+        } else {
+            library
         }
     }
 
-    /// The method `mount` adds a new library to the heap's compositer.
+    /// The method `mount` adds a new library to the heap's compositer,
+    /// a Compositer which installs in background lets the main thread
+    /// mount it once installed. A library new to the Neko.mount is
+    /// mounted without granted capability.
     /// @ libraryname: `arukana@libnya`.
     /// @ priority: `Some(-1)` or `None` for zero by default.
     pub fn mount<S: AsRef<OsStr>>(&mut self,
                                   libraryname: &S,
                                   priority: Option<i64>)
                                   -> Result<()> {
-        let old = env::current_dir()?;
-        let mount = self.git_with_lib()
+        if self.progress.is_some() {
+            return Ok(());
+        }
+        let granted: Option<Capabilities> = try!(self.get_mount())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .map_or(Some(Capabilities::default()), |mounted|
+                mounted.get_capabilities().cloned());
+        let mut mounted: Mounted = Mounted::new(true, priority);
+
+        if let Some(granted) = granted {
+            mounted.set_capabilities(granted);
+        }
+        self.git_with_lib()
             .and_then(|(git, lib)| {
-              let path = Path::new(git.to_str().unwrap());
-              let _ = env::set_current_dir(&path);
-              match self.unmount(libraryname) {
+              match self.detach(libraryname) {
                 Ok(_) |
                 Err(CompositerError::UnmountPosition) => {
                     let source: PathBuf = PathBuf::from(libraryname);
                     self.get_manifest(&git.join(&source))
-                        .and_then(|table| {
-                         if let Some(priority) =
-                            priority.or(parse_number!(table)) {
+                        .and_then(|manifest| {
                             match Library::new(
-                                lib.join(&source).with_extension(SPEC_LIB_EXT),
-                                priority,
-                                &self.state
+                                self.find_library(&lib, &source),
+                                priority.unwrap_or(manifest.get_priority()),
+                                mounted.get_given(manifest.get_capabilities()),
+                                &self.host,
+                                &mut self.state
                             ) {
                                 Err(why) => Err(CompositerError::Mount(why)),
                                 Ok(dy) => {
-                                    dy.start(&self.state);
                                     self.list.push(dy);
                                     self.list.sort();
-                                    Ok(())
+                                    self.set_mounted(libraryname, Some(mounted))
                                 },
                             }
-                        } else {
-                            Err(CompositerError::ParseInteger)
-                        }})
+                        })
                 },
                 Err(why) => Err(why),
-            }});
-        let _ = env::set_current_dir(old);
-        mount
+            }})
+    }
+
+    /// The method `remount` mounts a library with the priority
+    /// of the Neko.mount, a unmounted library stays unmounted.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn remount<S: AsRef<OsStr>>(&mut self, libraryname: &S) -> Result<()> {
+        let mounted: Option<Mounted> = try!(self.get_mount())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .cloned();
+
+        match mounted {
+            Some(ref mounted) if mounted.is_enabled().not() => Ok(()),
+            Some(mounted) => self.mount(libraryname, mounted.get_priority()),
+            None => self.mount(libraryname, None),
+        }
     }
 
-    /// The method `unmount` removes library from the queue.
+    /// The method `unmount` removes library from the queue
+    /// and remembers it as unmounted to the Neko.mount.
     /// @ libraryname: `arukana@libnya`.
     pub fn unmount<S: AsRef<OsStr>>(&mut self, libraryname: S) -> Result<()> {
-        if let Some(index) = self.list.iter().position(|s| {
+        let mounted: Mounted = try!(self.get_mount())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .cloned()
+            .unwrap_or_default();
+        let mut unmounted: Mounted = Mounted::new(false, mounted.get_priority());
+
+        if let Some(granted) = mounted.get_capabilities() {
+            unmounted.set_capabilities(*granted);
+        }
+        self.detach(&libraryname)
+            .and_then(|_| self.set_mounted(&libraryname, Some(unmounted)))
+    }
+
+    /// The method `grant` records the capabilities approved by the user
+    /// to the Neko.mount and remounts the library if it's mounted,
+    /// a library receives only the granted capabilities which
+    /// its manifest requests.
+    /// @ libraryname: `arukana@libnya`.
+    /// @ capabilities: `input,persona`.
+    pub fn grant<S: AsRef<OsStr>>(&mut self,
+                                  libraryname: &S,
+                                  capabilities: Capabilities)
+                                  -> Result<()> {
+        let mut mounted: Mounted = try!(self.get_mount())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .cloned()
+            .unwrap_or_else(|| Mounted::new(true, None));
+
+        mounted.set_capabilities(capabilities);
+        try!(self.set_mounted(libraryname, Some(mounted)));
+        if self.list.iter().any(|s| {
             s.as_path_buf().file_stem().eq(&Some(libraryname.as_ref()))
         }) {
-            self.list.remove(index);
-            self.list.sort();
+            self.mount(libraryname, mounted.get_priority())
+        } else {
             Ok(())
+        }
+    }
+
+    /// The accessor method `get_requested` returns the capabilities which
+    /// the manifest of a installed library requests and which aren't given.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn get_requested<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<Capabilities> {
+        let manifest: Manifest = try!(self.get_git().and_then(|git|
+            self.get_manifest(&git.join(libraryname.as_ref()))
+        ));
+        let requested: &Capabilities = manifest.get_capabilities();
+
+        Ok(requested.difference(&try!(self.get_mount())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .cloned()
+            .unwrap_or_default()
+            .get_given(requested)))
+    }
+
+    /// The method `reload` loads the rewritten dynamic library, then
+    /// ends the mounted library and starts the new one with the same
+    /// priority, the previous version keeps running if the new one
+    /// can't be loaded.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn reload<S: AsRef<OsStr>>(&mut self, libraryname: &S) -> Result<()> {
+        if let Some(index) = self.list.iter().position(|s| {
+            s.as_path_buf().file_stem().eq(&Some(libraryname.as_ref()))
+        }) {
+            let path: PathBuf = try!(self.get_lib()
+                                         .map(|lib| self.find_library(&lib, libraryname)));
+            let priority: i64 = self.list[index].get_priority();
+            let capabilities: Capabilities = *self.list[index].get_capabilities();
+
+            match Library::from_copy(path,
+                                     &self.get_root().join(SPEC_SUBD_COPY),
+                                     priority,
+                                     capabilities,
+                                     &self.host) {
+                Err(why) => Err(CompositerError::Reload(why)),
+                Ok(dy) => {
+                    self.list[index].end(&mut self.state);
+                    dy.start(&mut self.state);
+                    self.list[index] = dy;
+                    self.list.sort();
+                    Ok(())
+                },
+            }
         } else {
             Err(CompositerError::UnmountPosition)
         }
     }
 
-    /// The method `build` makes and adds a dynamic library
-    /// to SPEC_MANIFEST's destination.
-    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
-    /// @ sub: `arukana@libnya`.
-    pub fn build<S: AsRef<OsStr> + AsRef<Path>>(&mut self,
-                                                source: &PathBuf,
-                                                sub: S)
-                                                -> Result<()> {
-        let path = Path::new(source.to_str().unwrap());
-        let _ = env::set_current_dir(&path);
-        match self.get_lib() {
-            Err(why) => Err(why),
-            Ok(dest) => {
-                match process::Command::new("make")
-                    .current_dir(source.as_path())
-                    .status() {
-                    Err(why) => Err(CompositerError::BuildCommand(why)),
-                    Ok(status) => {
-                        if status.success() {
-                            fs::rename(source.join(only_rep!(sub))
-                                           .with_extension(SPEC_LIB_EXT),
-                                       dest.join(&sub)
-                                           .with_extension(SPEC_LIB_EXT))
-                                .or_else(|why: io::Error|
-                                    Err(CompositerError::MvFail(why)))
-                                .and_then(|_: ()|
-                                    self.mount(&sub, None)
-                                        .and(self.dependency(source)))
-                        } else {
-                            Err(CompositerError::BuildExit(status))
-                        }
+    /// The method `hot_reload` reloads the mounted libraries
+    /// rewritten to the lib sub-directory since the last call.
+    pub fn hot_reload(&mut self) {
+        self.reload_except(&[]);
+    }
+
+    /// The method `reload_except` reloads the mounted libraries
+    /// rewritten since the last call, but the swapped libraries
+    /// which are already remounted.
+    /// @ swapped: `["arukana@libnya"]`.
+    fn reload_except(&mut self, swapped: &[String]) {
+        let changes: Vec<PathBuf> = self.watch.as_ref()
+                                        .map(|watch| watch.changes())
+                                        .unwrap_or_default();
+
+        for path in changes {
+            if path.extension().map_or(false, |extension|
+                   extension.eq(SPEC_LIB_EXT) || extension.eq(SPEC_LIB_EXT_LEGACY)) {
+                if let Some(libraryname) = path.file_stem() {
+                    if swapped.iter().any(|name| OsStr::new(name).eq(libraryname)).not() {
+                        let _ = self.reload(&libraryname);
                     }
                 }
             }
         }
     }
 
-    fn dependency_from_git(&mut self,
-                           table: &toml::Table)
-                           -> Result<()>{
-        table.get("git").and_then(|git| {
-            git.as_str().and_then(|repo| match self.install(repo) {
-                Err(CompositerError::InstallExists) => {
-                    account_at_rep!(repo)
-                        .and_then(|sub| match self.update(&sub) {
-                            Ok(()) => None,
-                            Err(why) => Some(Err(why)),
-                        })
-                },
-                Ok(()) => None,
-                Err(why) => Some(Err(why)),
-            })
-        })
-        .unwrap_or_else(|| Ok(()))
+    /// The method `detach` ends and removes library from the queue.
+    /// @ libraryname: `arukana@libnya`.
+    fn detach<S: AsRef<OsStr>>(&mut self, libraryname: S) -> Result<()> {
+        if let Some(index) = self.list.iter().position(|s| {
+            s.as_path_buf().file_stem().eq(&Some(libraryname.as_ref()))
+        }) {
+            self.list.remove(index).end(&mut self.state);
+            self.list.sort();
+            Ok(())
+        } else {
+            Err(CompositerError::UnmountPosition)
+        }
     }
 
-    /// The method `dependency` lists the dependencies from
-    /// repository dynamic library and install.
-    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
-    pub fn dependency(&mut self, source: &PathBuf) -> Result<()> {
-        self.get_manifest(source).and_then(|table|
-                                           table.get("dependencies")
-                                           .and_then(|deps|
-                                                     deps.as_table().and_then(|table|
-                                                                              table.into_iter()
-                                                                              .filter_map(|dep|
-                                                                                          dep.1.as_table()
-                                                                                          .and_then(|table|
-                                                                                                    Some(self.dependency_from_git(table))))
-                                                                              .next()))
-                                           .unwrap_or_else(|| Ok(())))
+    /// The method `installed` calls the hook `install` of the newly
+    /// installed libraries which are mounted.
+    /// @ libraries: `["meow@libmeow", "arukana@libnya"]`.
+    fn installed(&mut self, libraries: &[String]) {
+        for dy in self.list.iter() {
+            if libraries.iter().any(|libraryname| {
+                dy.as_path_buf().file_stem().eq(&Some(OsStr::new(libraryname)))
+            }) {
+                dy.install(&mut self.state);
+            }
+        }
     }
 
-    /// The methodd `install` clones and makes a dynamic library from repository
-    /// and recursive call the dependencies.
-    /// @ repo: `https://github.com/Arukana/libnya.git`.
-    pub fn install(&mut self, repo: &str) -> Result<()> {
-        self.get_git()
-            .and_then(|git|
-                account_at_rep!(repo)
-                    .and_then(|sub| Some({
-                        let dest: PathBuf = git.join(&sub);
-                        if dest.exists() {
-                            Err(CompositerError::InstallExists)
-                        } else {
-                            match git2::Repository::clone(repo, &dest) {
-                                Err(why) => Err(CompositerError::InstallClone(why)),
-                                Ok(_) => self.build(&dest, &sub),
-                            }
-                        }
-                    }))
-                    .unwrap_or_else(|| Err(CompositerError::InstallFormat)))
+    /// The accessor method `get_build` returns the `[build]` section
+    /// from the manifest of a repository.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    pub fn get_build(&self, source: &PathBuf) -> Result<Build> {
+        self.get_manifest(source)
+            .and_then(|manifest| Ok(manifest.get_build().clone()))
     }
 
-    fn reset(&self,
-             repo: &git2::Repository,
-             object: &git2::Object)
-             -> Result<()> {
-        match repo.reset(object, git2::ResetType::Hard, None) {
-            Err(why) => Err(CompositerError::UpdateRepReset(why)),
-            Ok(_) => Ok(()),
+    /// The method `compile` runs the build backend and moves
+    /// the dynamic library to the lib sub-directory.
+    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
+    /// @ sub: `arukana@libnya`.
+    fn compile<S: AsRef<OsStr> + AsRef<Path>>(&self,
+                                              source: &PathBuf,
+                                              sub: &S)
+                                              -> Result<()> {
+        let dest: PathBuf = try!(self.get_lib());
+        let artifact: PathBuf = try!(self.make(source, sub));
+        let library: PathBuf = dest.join(sub).with_extension(SPEC_LIB_EXT);
+        let legacy: PathBuf = library.with_extension(SPEC_LIB_EXT_LEGACY);
+
+        if legacy.ne(&library) {
+            let _ = fs::remove_file(legacy);
         }
+        fs::rename(artifact, library)
+            .or_else(|why: io::Error|
+                Err(CompositerError::MvFail(why)))
     }
 
-    fn update_from_master(&self, repo: &git2::Repository) -> Result<()> {
-        match repo.find_branch("master", git2::BranchType::Local) {
-            Err(why) => Err(CompositerError::UpdateRepBranch(why)),
-            Ok(branch) => {
-                match branch.get().target() {
-                    None => Err(CompositerError::UpdateRepBranchId),
-                    Some(id) => {
-                        match repo.find_object(id, None) {
-                            Err(why) => {
-                                Err(CompositerError::UpdateRepObject(why))
-                            }
-                            Ok(obj) => self.reset(repo, &obj),
-                        }
-                    }
-                }
-            }
+    /// The method `make` runs the build backend and returns
+    /// the produced dynamic library, the repository is verified
+    /// once where it's cloned or checked out.
+    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
+    /// @ sub: `arukana@libnya`.
+    fn make<S: AsRef<OsStr> + AsRef<Path>>(&self,
+                                           source: &PathBuf,
+                                           sub: &S)
+                                           -> Result<PathBuf> {
+        let manifest: Manifest = try!(self.get_manifest(source));
+        let build: &Build = manifest.get_build();
+        let neko: semver::Version = try!(parse_version(env!("CARGO_PKG_VERSION")));
+
+        if manifest.is_supported(&neko).not() {
+            return Err(CompositerError::ManifestNeko(
+                manifest.get_neko().map(|neko| neko.to_string()).unwrap_or_default()
+            ));
         }
-    }
 
-    /// The method `update` hard-resets the master branch to last commit.
-    /// @ libraryname: `arukana@libnya`.
-    pub fn update(&mut self, libraryname: &str) -> Result<()> {
-        self.get_git().and_then(|git| {
-            let dest: PathBuf = git.join(&libraryname);
-            match git2::Repository::open(&dest) {
-                Err(why) => Err(CompositerError::UpdateRepOpen(why)),
-                Ok(rep) => {
-                    match rep.find_remote("origin") {
-                        Err(why) => {
-                            Err(CompositerError::UpdateRepOrigin(why))
-                        }
-                        Ok(mut remote) => {
-                            if let Some(why) =
-                                remote.fetch(&["refs/heads/*:refs/heads/*"],
-                                           None,
-                                           None)
-                                    .err() {
-                                Err(CompositerError::UpdateRepFetch(why))
-                            } else {
-                                self.update_from_master(&rep)
-                                    .and_then(|_| {
-                                        self.build(&dest, &libraryname)
-                                    })
-                            }
-                        }
+        let include: PathBuf = try!(self.header());
+        let log: PathBuf = try!(self.get_log()).join(sub).with_extension(SPEC_LOG_EXT);
+        let mut descripter: File = try!(File::create(&log).map_err(CompositerError::BuildLog));
+
+        for mut command in build.get_backend().get_commands(source) {
+            command.env(SPEC_INCLUDE, &include);
+            match progress::run(&mut command, &mut descripter, |line|
+                self.report(line.to_string())
+            ) {
+                Err(why) => return Err(CompositerError::BuildCommand(why)),
+                Ok(status) => {
+                    if status.success().not() {
+                        return Err(CompositerError::BuildExit(status));
                     }
                 }
             }
-        })
+        }
+        build.find_artifact(source, only_rep!(sub))
+             .ok_or(CompositerError::BuildArtifact)
     }
 
-    /// The method `uninstall` removes library from the filesystem with
-    /// the source.
-    /// @libraryname: `arukana@libnya`.
-    pub fn uninstall<S: AsRef<OsStr>>(&mut self,
-                                      libraryname: &S)
-                                      -> Result<()> {
-        match self.unmount(libraryname) {
+    /// The method `build` verifies that the library is trusted, makes and
+    /// mounts the dependencies, then makes and adds a dynamic library to
+    /// SPEC_MANIFEST's destination and pins it to the Neko.lock.
+    /// The just mounted libraries aren't reloaded by the next `hot_reload`.
+    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
+    /// @ sub: `arukana@libnya`.
+    pub fn build<S: AsRef<OsStr> + AsRef<Path>>(&mut self,
+                                                source: &PathBuf,
+                                                sub: S)
+                                                -> Result<()> {
+        let name: String = AsRef::<OsStr>::as_ref(&sub).to_str()
+                                                       .unwrap_or_default()
+                                                       .to_string();
+        let result: Result<()> = self.verify(source, &sub)
+            .and_then(|_: ()| self.dependency(source))
+            .and_then(|_: ()| self.compile(source, &sub))
+            .and_then(|_: ()| self.remount(&sub))
+            .and_then(|_: ()| self.lock(&sub));
+        let compiled: Vec<String> = self.get_lock()
+                                        .and_then(|lock| lock.get_graph().order(&name))
+                                        .unwrap_or_else(|_| vec![name.clone()]);
+
+        self.reload_except(&compiled);
+        result
+    }
+
+    /// The method `checksum` returns the blob identifiant of
+    /// a built dynamic library.
+    /// @ path: `$HOME/.neko/lib/arukana@libnya.dylib`.
+    fn checksum(&self, path: &PathBuf) -> Result<String> {
+        let mut buffer: Vec<u8> = Vec::new();
+
+        match File::open(path).and_then(|mut descripter|
+            descripter.read_to_end(&mut buffer)
+        ) {
+            Err(why) => Err(CompositerError::ReadLib(why)),
+            Ok(_) => {
+                match git2::Oid::hash_object(git2::ObjectType::Blob, &buffer) {
+                    Err(why) => Err(CompositerError::Checksum(why)),
+                    Ok(id) => Ok(id.to_string()),
+                }
+            }
+        }
+    }
+
+    /// The method `lock` pins the current commit, the dependencies
+    /// and the checksum of a installed library to the Neko.lock.
+    /// @ libraryname: `arukana@libnya`.
+    fn lock<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<()> {
+        self.get_locked(libraryname)
+            .and_then(|locked| self.set_locked(libraryname, locked))
+    }
+
+    /// The method `get_locked` returns the pin of the current commit,
+    /// the dependencies and the checksum of a installed library.
+    /// @ libraryname: `arukana@libnya`.
+    fn get_locked<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<Locked> {
+        let libraryname: &str = libraryname.as_ref().to_str().unwrap_or_default();
+        let (git, lib): (PathBuf, PathBuf) = try!(self.git_with_lib());
+        let source: PathBuf = git.join(libraryname);
+        let (url, revision, commit): (String, String, String) =
+            if self.is_linked(&source) {
+                (Source::Local(try!(fs::canonicalize(&source)
+                    .map_err(CompositerError::InstallLocal))).to_string(),
+                 String::new(),
+                 git2::Repository::open(&source)
+                     .and_then(|repo| repo.refname_to_id("HEAD"))
+                     .map(|id| id.to_string())
+                     .unwrap_or_default())
+            } else {
+                let repo: git2::Repository = try!(git2::Repository::open(&source)
+                    .map_err(CompositerError::UpdateRepOpen));
+                let url: String = try!(repo.find_remote("origin")
+                    .map_err(CompositerError::UpdateRepOrigin))
+                    .url()
+                    .unwrap_or_default()
+                    .to_string();
+                let commit: git2::Oid = try!(repo.refname_to_id("HEAD")
+                    .map_err(CompositerError::LockRepHead));
+
+                (url, self.get_revision(&repo).to_string(), commit.to_string())
+            };
+        let dependencies: Vec<String> = try!(self.get_dependencies(&source))
+            .iter()
+            .filter_map(|&(ref repo, _)| repo.parse::<Source>().ok())
+            .filter_map(|source| source.get_libraryname().ok())
+            .collect::<Vec<String>>();
+        let checksum: String = try!(self.checksum(
+            &self.find_library(&lib, &libraryname)
+        ));
+        let mut locked: Locked = Locked::new(url, revision, commit, dependencies, checksum);
+
+        locked.set_dependency(try!(self.get_lock())
+                                  .get(libraryname)
+                                  .map_or(false, |locked| locked.is_dependency()));
+        Ok(locked)
+    }
+
+    /// The method `set_locked` records the pin of a library to the Neko.lock,
+    /// a Compositer which installs in background sends it to the main thread.
+    /// @ libraryname: `arukana@libnya`.
+    fn set_locked<S: AsRef<OsStr>>(&self, libraryname: &S, locked: Locked) -> Result<()> {
+        let path: PathBuf = self.get_root().join(SPEC_LOCK);
+        let libraryname: &str = libraryname.as_ref().to_str().unwrap_or_default();
+
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Lock(libraryname.to_string(), Some(locked)));
+            return Ok(());
+        }
+        let mut lock: Lock = try!(Lock::from_path(&path));
+
+        lock.insert(libraryname.to_string(), locked);
+        lock.save(&path)
+    }
+
+    /// The method `lock_dependency` pins a library like `lock` and
+    /// remembers a newly pinned library as installed only as a dependency.
+    /// @ libraryname: `arukana@libmeow`.
+    fn lock_dependency<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<()> {
+        let pinned: bool = try!(self.get_lock())
+            .get(libraryname.as_ref().to_str().unwrap_or_default())
+            .is_some();
+        let mut locked: Locked = try!(self.get_locked(libraryname));
+
+        if pinned.not() {
+            locked.set_dependency(true);
+        }
+        self.set_locked(libraryname, locked)
+    }
+
+    /// The method `unlock` unpins a library from the Neko.lock.
+    /// A Compositer which installs in background sends it to
+    /// the main thread.
+    /// @ libraryname: `arukana@libnya`.
+    fn unlock<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<()> {
+        let path: PathBuf = self.get_root().join(SPEC_LOCK);
+
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Lock(libraryname.as_ref().to_str()
+                                                    .unwrap_or_default()
+                                                    .to_string(), None));
+            return Ok(());
+        }
+        let mut lock: Lock = try!(Lock::from_path(&path));
+
+        lock.remove(libraryname.as_ref().to_str().unwrap_or_default());
+        lock.save(&path)
+    }
+
+    /// The method `list` returns a line by installed library with
+    /// its mounted state, priority, dynamic library and current commit.
+    pub fn list(&self) -> Result<String> {
+        let (git, lib): (PathBuf, PathBuf) = try!(self.git_with_lib());
+        let mount: Mount = try!(self.get_mount());
+        let mut librarynames: Vec<String> = match fs::read_dir(&git) {
+            Err(why) => return Err(CompositerError::ReadDirGit(why)),
+            Ok(entries) => {
+                entries.filter_map(|entry| entry.ok())
+                       .filter_map(|entry| entry.file_name().into_string().ok())
+                       .collect::<Vec<String>>()
+            },
+        };
+
+        librarynames.sort();
+        Ok(librarynames.iter().map(|libraryname| {
+            let mounted: Option<&Library> = self.list.iter().find(|dy|
+                dy.as_path_buf().file_stem().eq(&Some(OsStr::new(libraryname)))
+            );
+            let priority: Option<i64> =
+                mounted.map(|dy| dy.get_priority())
+                       .or_else(|| mount.get(libraryname)
+                                        .and_then(|mounted| mounted.get_priority()))
+                       .or_else(|| self.get_manifest(&git.join(libraryname))
+                                       .ok()
+                                       .map(|manifest| manifest.get_priority()));
+            let commit: String = git2::Repository::open(git.join(libraryname))
+                .and_then(|repo| repo.refname_to_id("HEAD"))
+                .map(|id| id.to_string())
+                .unwrap_or_else(|_| String::from("-"));
+
+            format!("{} {} {} {} {}",
+                    libraryname,
+                    if mounted.is_some() { "mounted" } else { "unmounted" },
+                    priority.map_or(String::from("-"), |priority| priority.to_string()),
+                    self.find_library(&lib, libraryname).display(),
+                    commit)
+        }).collect::<Vec<String>>().join("\n"))
+    }
+
+    /// The method `info` returns the manifest's fields, the dependencies
+    /// and the extern functions exported by a installed library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn info<S: AsRef<OsStr>>(&self, libraryname: &S) -> Result<String> {
+        let (git, lib): (PathBuf, PathBuf) = try!(self.git_with_lib());
+        let source: PathBuf = git.join(libraryname.as_ref());
+        let manifest: Manifest = try!(self.get_manifest(&source));
+        let hooks: Vec<&'static str> = try!(
+            Library::get_hooks(&self.find_library(&lib, libraryname))
+                    .map_err(CompositerError::Info)
+        );
+        let mut lines: Vec<String> = Vec::new();
+
+        if let Some(name) = manifest.get_name() {
+            lines.push(format!("name: {}", name));
+        }
+        if let Some(version) = manifest.get_version() {
+            lines.push(format!("version: {}", version));
+        }
+        if manifest.get_authors().is_empty().not() {
+            lines.push(format!("authors: {}", manifest.get_authors().join(", ")));
+        }
+        if let Some(description) = manifest.get_description() {
+            lines.push(format!("description: {}", description));
+        }
+        if let Some(neko) = manifest.get_neko() {
+            lines.push(format!("neko: {}", neko));
+        }
+        lines.push(format!("priority: {}", manifest.get_priority()));
+        lines.push(format!("build: {:?}", manifest.get_build().get_backend()));
+        lines.push(format!("dependencies: {}",
+                           manifest.get_dependencies().iter()
+                               .map(|&(ref name, ref dependency)| match dependency.get_version() {
+                                   Some(version) => format!("{} {} ({})", name,
+                                                            dependency.get_git(), version),
+                                   None => format!("{} {}", name, dependency.get_git()),
+                               })
+                               .collect::<Vec<String>>()
+                               .join(", ")));
+        lines.push(format!("hooks: {}", hooks.join(", ")));
+        lines.push(format!("capabilities: {}", manifest.get_capabilities()));
+        lines.push(format!("granted: {}",
+                           try!(self.get_mount())
+                               .get(libraryname.as_ref().to_str().unwrap_or_default())
+                               .cloned()
+                               .unwrap_or_default()
+                               .get_given(manifest.get_capabilities())));
+        Ok(lines.join("\n"))
+    }
+
+    /// The accessor method `get_dependencies` returns the sources
+    /// and the optional version requirements of dependencies from
+    /// the manifest of a repository.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    pub fn get_dependencies(&self,
+                            source: &PathBuf)
+                            -> Result<Vec<(String, Option<String>)>> {
+        self.get_manifest(source).and_then(|manifest|
+            Ok(manifest.get_dependencies()
+                       .iter()
+                       .map(|&(_, ref dependency)| (
+                           dependency.get_git().to_string(),
+                           dependency.get_version().map(|version| version.to_string())
+                       ))
+                       .collect::<Vec<(String, Option<String>)>>()))
+    }
+
+    /// The accessor method `get_version` returns the version
+    /// from the manifest of a repository if declared.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    pub fn get_version(&self, source: &PathBuf) -> Result<Option<semver::Version>> {
+        self.get_manifest(source)
+            .and_then(|manifest| Ok(manifest.get_version().cloned()))
+    }
+
+    /// The method `satisfies` returns true if the checked out version
+    /// of a library matches every requirement of its dependents.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    /// @ sub: `arukana@libnya`.
+    fn satisfies(&self,
+                 source: &PathBuf,
+                 sub: &str,
+                 requirements: &Requirements)
+                 -> bool {
+        requirements.get(sub).is_empty() ||
+        self.get_version(source)
+            .ok()
+            .and_then(|version| version)
+            .map_or(false, |version| requirements.matches(sub, &version))
+    }
+
+    /// The method `is_linked` returns true if the library
+    /// is a link to a local directory.
+    /// @ dest: `$HOME/.neko/git/local@libnya`.
+    fn is_linked(&self, dest: &PathBuf) -> bool {
+        fs::symlink_metadata(dest)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false)
+    }
+
+    /// The method `link` links a local directory
+    /// to the git sub-directory.
+    /// @ path: `./libnya`.
+    /// @ dest: `$HOME/.neko/git/local@libnya`.
+    fn link(&self, path: &PathBuf, dest: &PathBuf) -> Result<()> {
+        fs::canonicalize(path)
+            .and_then(|path| unix::fs::symlink(path, dest))
+            .or_else(|why: io::Error| Err(CompositerError::InstallLocal(why)))
+    }
+
+    /// The method `locate` returns the repository of a library from
+    /// the staging sub-directory, else the installed one.
+    /// @ git: `$HOME/.neko/git` or the staging sub-directory.
+    /// @ sub: `arukana@libnya`.
+    fn locate(&self, git: &PathBuf, sub: &str) -> PathBuf {
+        let dest: PathBuf = git.join(sub);
+
+        if fs::symlink_metadata(&dest).is_ok() {
+            dest
+        } else {
+            self.get_git().map(|installed| installed.join(sub)).unwrap_or(dest)
+        }
+    }
+
+    /// The method `fetch_dependency` clones a missing dependency or
+    /// fetches and hard-resets a installed dependency to its revision.
+    /// A dependency with version requirements is checked out at
+    /// the highest matching tag unless the installed version
+    /// already matches. A local dependency is linked and never
+    /// fetched. To the staging sub-directory, a installed dependency
+    /// which already matches is reused instead of cloned.
+    /// @ git: `$HOME/.neko/git`.
+    /// @ source: `https://github.com/Arukana/libnya.git`.
+    /// @ sub: `arukana@libnya`.
+    fn fetch_dependency(&self,
+                        git: &PathBuf,
+                        source: &Source,
+                        sub: &str,
+                        requirements: &Requirements)
+                        -> Result<()> {
+        let dest: PathBuf = git.join(sub);
+        let installed: PathBuf = self.locate(git, sub);
+
+        if installed.ne(&dest) && installed.exists() &&
+           self.satisfies(&installed, sub, requirements) {
+            return Ok(());
+        }
+        let (repo, revision): (&str, Revision) = match *source {
+            Source::Git(ref repo, ref revision) => (&repo[..], revision.clone()),
+            Source::Local(ref path) => {
+                if self.is_linked(&dest).not() {
+                    try!(self.set_ungranted(&sub));
+                    try!(self.link(path, &dest));
+                }
+                return if self.satisfies(&dest, sub, requirements) {
+                    Ok(())
+                } else {
+                    requirements.select(sub, Vec::new()).and(Ok(()))
+                };
+            },
+        };
+        let installed: bool = dest.exists();
+        let rep: git2::Repository = if installed {
+            try!(git2::Repository::open(&dest)
+                .map_err(CompositerError::UpdateRepOpen))
+        } else {
+            try!(self.set_ungranted(&sub));
+            try!(self.clone_revision(repo, &dest, &revision))
+        };
+
+        if requirements.get(sub).is_empty() {
+            let revision: Revision = if installed {
+                try!(self.fetch(&rep));
+                self.get_revision(&rep)
+            } else {
+                revision
+            };
+            self.checkout_revision(&rep, &revision)
+        } else if installed && self.satisfies(&dest, sub, requirements) {
+            Ok(())
+        } else {
+            if installed {
+                try!(self.fetch(&rep));
+            }
+            let tags: git2::string_array::StringArray = try!(rep.tag_names(None)
+                .map_err(CompositerError::UpdateRepRevision));
+            let tag: String = try!(requirements.select(
+                sub, tags.iter().filter_map(|tag| tag)
+            ));
+
+            self.checkout_revision(&rep, &Revision::Tag(tag))
+        }
+    }
+
+    /// The method `resolve_dependencies` walks the whole graph of
+    /// dependencies from a cloned library, fetches every dependency
+    /// at a version which satisfies its dependents and returns
+    /// the library names in topological order, with the reused
+    /// installed dependencies.
+    /// @ git: `$HOME/.neko/git` or the staging sub-directory.
+    /// @ libraryname: `arukana@libnya`.
+    fn resolve_dependencies(&self, git: &PathBuf, libraryname: &str) -> Result<Vec<String>> {
+        let mut graph: Graph = Graph::default();
+        let mut requirements: Requirements = Requirements::default();
+        let mut queue: Vec<(String, Option<Source>)> =
+            vec![(libraryname.to_string(), None)];
+
+        while let Some((sub, source)) = queue.pop() {
+            if graph.contains(&sub) &&
+               (source.is_none() || self.satisfies(&self.locate(git, &sub), &sub, &requirements)) {
+                continue;
+            }
+            if let Some(source) = source {
+                try!(self.fetch_dependency(git, &source, &sub, &requirements));
+            }
+            let mut dependencies: Vec<String> = Vec::new();
+            for (repo, version) in try!(self.get_dependencies(&self.locate(git, &sub))) {
+                let source: Source = try!(repo.parse::<Source>())
+                                         .rebase(&self.locate(git, &sub));
+                let dependency: String = try!(source.get_libraryname());
+
+                if let Some(version) = version {
+                    try!(requirements.insert(dependency.clone(), sub.clone(), &version));
+                }
+                queue.push((dependency.clone(), Some(source)));
+                dependencies.push(dependency);
+            }
+            graph.insert(sub, dependencies);
+        }
+        graph.order(libraryname)
+    }
+
+    /// The method `dependency` resolves the dependencies from
+    /// repository dynamic library, then makes and mounts them
+    /// in topological order.
+    /// @ source: `$HOME/.neko/git/Arukana@libnya`.
+    pub fn dependency(&mut self, source: &PathBuf) -> Result<()> {
+        let git: PathBuf = try!(self.get_git());
+        let libraryname: String = source.file_name()
+                                        .and_then(|name| name.to_str())
+                                        .unwrap_or_default()
+                                        .to_string();
+
+        for dependency in try!(self.resolve_dependencies(&git, &libraryname)) {
+            if dependency.ne(&libraryname) {
+                try!(self.trust_dependency(&git.join(&dependency), &dependency)
+                         .and_then(|_| self.compile(&git.join(&dependency), &dependency))
+                         .and_then(|_| self.remount(&dependency))
+                         .and_then(|_| self.lock_dependency(&dependency)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The methodd `install` clones and makes a dynamic library from repository
+    /// and recursive call the dependencies.
+    /// @ repo: `https://github.com/Arukana/libnya.git` with a optional revision
+    ///         like `#v1.2.0`, `#tag=v1.2.0`, `#branch=dev` or `#rev=abc123`,
+    ///         or a local directory like `./libnya` or `file:///home/neko/libnya`,
+    ///         or a short name from the registry like `libnya#v1.2.0`.
+    pub fn install(&mut self, repo: &str) -> Result<()> {
+        self.install_granted(repo, Capabilities::default())
+            .map(|_| ())
+    }
+
+    /// The method `install_background` installs a library like
+    /// `install_granted` without blocking, the thread only clones and
+    /// builds and sends its changes of the Neko.mount, the Neko.lock and
+    /// the Neko.trust, which `call` writes before mounting the installed
+    /// libraries.
+    /// @ repo: `https://github.com/Arukana/libnya.git`.
+    /// @ capabilities: `input,persona`.
+    pub fn install_background(&mut self,
+                              repo: &str,
+                              capabilities: Capabilities)
+                              -> Result<()> {
+        if self.installing.is_some() {
+            Err(CompositerError::InstallBusy)
+        } else {
+            let (sender, receiver): (mpsc::Sender<Progress>, mpsc::Receiver<Progress>) =
+                mpsc::channel();
+            let root: PathBuf = self.get_root();
+            let repo: String = repo.to_string();
+
+            thread::spawn(move || {
+                let mut compositer: Compositer = Compositer::default();
+
+                compositer.root = root;
+                compositer.progress = Some(Reporter::new(sender.clone()));
+                let result: Result<String> = compositer.install_granted(&repo, capabilities);
+                let locked: Vec<String> = compositer.progress.as_ref()
+                                                    .map(|progress| progress.get_locked())
+                                                    .unwrap_or_default();
+                let _ = sender.send(Progress::End(repo, result, locked));
+            });
+            self.installing = Some(receiver);
+            self.approved = capabilities;
+            self.untrusted = None;
+            Ok(())
+        }
+    }
+
+    /// The method `poll_install` shows to the tooltip the progress of
+    /// the installation in background and writes its changes, then
+    /// mounts its libraries once ended and asks to approve the
+    /// capabilities requested by the library which aren't granted,
+    /// or to trust a untrusted repository with `confirm`.
+    /// It returns true while the installation runs.
+    pub fn poll_install(&mut self) -> bool {
+        loop {
+            let message: Progress = match self.installing.as_ref()
+                                              .map(|installing| installing.try_recv()) {
+                None | Some(Err(mpsc::TryRecvError::Empty)) => break,
+                Some(Err(mpsc::TryRecvError::Disconnected)) => {
+                    self.installing = None;
+                    self.state.set_tooltip_message(
+                        "The installation has stopped without ending.".to_string()
+                    );
+                    break;
+                },
+                Some(Ok(message)) => message,
+            };
+            match message {
+                Progress::Line(line) => self.state.set_tooltip_message(line),
+                Progress::Mount(libraryname, mounted) => {
+                    if let Err(why) = self.set_mounted(&libraryname, mounted) {
+                        self.state.set_tooltip_message(
+                            format!("Can't mount \"{}\" because: {}", libraryname, why)
+                        );
+                    }
+                },
+                Progress::Lock(libraryname, locked) => {
+                    if let Err(why) = match locked {
+                        Some(locked) => self.set_locked(&libraryname, locked),
+                        None => self.unlock(&libraryname),
+                    } {
+                        self.state.set_tooltip_message(
+                            format!("Can't lock \"{}\" because: {}", libraryname, why)
+                        );
+                    }
+                },
+                Progress::Trust(account) => {
+                    if let Err(why) = self.trust(&account) {
+                        self.state.set_tooltip_message(
+                            format!("Can't trust \"{}\" because: {}", account, why)
+                        );
+                    }
+                },
+                Progress::Fingerprint(fingerprint) => {
+                    if let Err(why) = self.trust_fingerprint(&fingerprint) {
+                        self.state.set_tooltip_message(
+                            format!("Can't trust \"{}\" because: {}", fingerprint, why)
+                        );
+                    }
+                },
+                Progress::Host(host) => {
+                    if let Err(why) = self.trust_host(&host) {
+                        self.state.set_tooltip_message(
+                            format!("Can't trust \"{}\" because: {}", host, why)
+                        );
+                    }
+                },
+                Progress::End(repo, Err(CompositerError::Untrusted(account, url, commit)), _) => {
+                    self.installing = None;
+                    self.state.set_tooltip_message(format!(
+                        "The repository {} at {} isn't trusted, trust {} and install it? [y/N]",
+                        url, commit, account
+                    ));
+                    self.untrusted = Some((account, repo, self.approved));
+                },
+                Progress::End(repo, Err(why), _) => {
+                    self.installing = None;
+                    self.state.set_tooltip_message(
+                        format!("Can't install \"{}\" because: {}", repo, why)
+                    );
+                },
+                Progress::End(repo, Ok(sub), locked) => {
+                    self.installing = None;
+                    for libraryname in &locked {
+                        if self.list.iter().any(|s| {
+                            s.as_path_buf().file_stem().eq(&Some(OsStr::new(libraryname)))
+                        }).not() {
+                            let _ = self.remount(libraryname);
+                        }
+                    }
+                    self.reload_except(&locked);
+                    self.installed(&locked);
+                    match self.get_requested(&sub) {
+                        Ok(ref requested) if requested.is_empty().not() => {
+                            self.state.set_tooltip_message(format!(
+                                "{} installed, it requests {}: approve with `neko grant {}`.",
+                                repo, requested, sub
+                            ))
+                        },
+                        _ => self.state.set_tooltip_message(
+                            format!("{} installed with success.", repo)
+                        ),
+                    }
+                },
+            }
+        }
+        self.installing.is_some()
+    }
+
+    /// The method `get_untrusted` returns the account to confirm for
+    /// the installation in background refused because it's untrusted.
+    pub fn get_untrusted(&self) -> Option<&str> {
+        self.untrusted.as_ref().map(|&(ref account, _, _)| account.as_str())
+    }
+
+    /// The method `confirm` answers the question asked for a untrusted
+    /// repository, once accepted the account is trusted and
+    /// the installation restarts in background.
+    pub fn confirm(&mut self, accepted: bool) -> Result<()> {
+        match self.untrusted.take() {
+            Some((account, repo, capabilities)) if accepted => {
+                self.trust(&account)
+                    .and_then(|_| self.install_background(&repo, capabilities))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// The accessor method `get_legacy` returns the libraries mounted
+    /// before the capabilities which aren't granted yet.
+    pub fn get_legacy(&self) -> &[String] {
+        self.legacy.as_slice()
+    }
+
+    /// The method `migrate` answers once the question asked for the libraries
+    /// mounted before the capabilities: once accepted each library is granted
+    /// the capabilities requested by its manifest, else nothing.
+    pub fn migrate(&mut self, accepted: bool) -> Result<()> {
+        let legacy: Vec<String> = self.legacy.drain(..).collect::<Vec<String>>();
+
+        for libraryname in &legacy {
+            let capabilities: Capabilities = if accepted {
+                self.get_git()
+                    .and_then(|git| self.get_manifest(&git.join(libraryname)))
+                    .map(|manifest| *manifest.get_capabilities())
+                    .unwrap_or_default()
+            } else {
+                Capabilities::default()
+            };
+
+            try!(self.grant(libraryname, capabilities));
+        }
+        Ok(())
+    }
+
+    /// The method `install_granted` installs a library like `install`
+    /// with the capabilities approved by the user and returns its name,
+    /// the capabilities requested by its manifest and not approved
+    /// are never given.
+    /// @ repo: `https://github.com/Arukana/libnya.git`.
+    /// @ capabilities: `input,persona`.
+    pub fn install_granted(&mut self,
+                           repo: &str,
+                           capabilities: Capabilities)
+                           -> Result<String> {
+        let source: Source = try!(self.get_source(repo));
+        let sub: String = try!(source.get_libraryname());
+        let dest: PathBuf = try!(self.get_git()).join(&sub);
+
+        if fs::symlink_metadata(&dest).is_ok() {
+            Err(CompositerError::InstallExists)
+        } else {
+            let before: Lock = try!(self.get_lock());
+
+            try!(self.grant(&sub, capabilities));
+            let fetched: Result<()> = match source {
+                Source::Local(ref path) => self.link(path, &dest),
+                Source::Git(ref repo, ref revision) => {
+                    self.clone_revision(repo, &dest, revision)
+                        .and_then(|rep| self.checkout_revision(&rep, revision))
+                },
+            };
+            fetched.and_then(|_| self.build(&dest, &sub))
+                   .map(|_| {
+                       let added: Vec<String> = self.get_lock()
+                           .and_then(|lock| lock.get_graph().order(&sub))
+                           .unwrap_or_else(|_| vec![sub.clone()])
+                           .into_iter()
+                           .filter(|libraryname| before.get(libraryname).is_none())
+                           .collect::<Vec<String>>();
+
+                       self.installed(&added);
+                       sub.clone()
+                   })
+                   .or_else(|why| {
+                       let _ = self.set_mounted(&sub, None);
+                       if let CompositerError::Untrusted(..) = why {
+                           let _ = fs::remove_dir_all(&dest);
+                       }
+                       Err(why)
+                   })
+        }
+    }
+
+    /// The method `clone_revision` clones a repository on the branch
+    /// of the revision or else on the default branch of the origin.
+    /// @ repo: `https://github.com/Arukana/libnya.git`.
+    /// @ dest: `$HOME/.neko/git/arukana@libnya`.
+    fn clone_revision(&self,
+                      repo: &str,
+                      dest: &PathBuf,
+                      revision: &Revision)
+                      -> Result<git2::Repository> {
+        let mut builder: git2::build::RepoBuilder = git2::build::RepoBuilder::new();
+
+        if let Revision::Branch(ref branch) = *revision {
+            builder.branch(branch);
+        }
+        self.report(format!("Cloning {}", repo));
+        builder.fetch_options(self.get_fetch_options())
+               .clone(repo, dest)
+               .or_else(|why: git2::Error| Err(CompositerError::InstallClone(why)))
+    }
+
+    /// The accessor method `get_fetch_options` returns the options
+    /// of a download which reports the git2's transfer progress.
+    fn get_fetch_options<'a>(&'a self) -> git2::FetchOptions<'a> {
+        let mut callbacks: git2::RemoteCallbacks<'a> = git2::RemoteCallbacks::new();
+        let mut last: Option<usize> = None;
+        let mut options: git2::FetchOptions<'a> = git2::FetchOptions::new();
+
+        callbacks.transfer_progress(move |stats: git2::Progress| {
+            let percent: usize = if stats.total_objects().eq(&0) {
+                0
+            } else {
+                stats.received_objects() * 100 / stats.total_objects()
+            };
+
+            if last.ne(&Some(percent)) {
+                last = Some(percent);
+                self.report(format!("Receiving objects: {}% ({}/{})",
+                                    percent,
+                                    stats.received_objects(),
+                                    stats.total_objects()));
+            }
+            true
+        });
+        options.remote_callbacks(callbacks);
+        options
+    }
+
+    /// The method `report` sends a line of progress from
+    /// a Compositer which installs in background.
+    /// @ line: `Receiving objects: 45% (123/270)`.
+    fn report(&self, line: String) {
+        if let Some(ref progress) = self.progress {
+            progress.send(Progress::Line(line));
+        }
+    }
+
+    /// The method `fetch` downloads the branches and the tags
+    /// from the origin.
+    fn fetch(&self, repo: &git2::Repository) -> Result<()> {
+        match repo.find_remote("origin") {
+            Err(why) => Err(CompositerError::UpdateRepOrigin(why)),
+            Ok(mut remote) => {
+                remote.fetch(&["refs/heads/*:refs/heads/*",
+                               "refs/tags/*:refs/tags/*"],
+                             Some(&mut self.get_fetch_options()), None)
+                      .or_else(|why: git2::Error|
+                          Err(CompositerError::UpdateRepFetch(why)))
+            }
+        }
+    }
+
+    fn reset(&self,
+             repo: &git2::Repository,
+             object: &git2::Object)
+             -> Result<()> {
+        match repo.reset(object, git2::ResetType::Hard, None) {
+            Err(why) => Err(CompositerError::UpdateRepReset(why)),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// The accessor method `get_revision` returns the revision
+    /// remembered by a repository or the default branch.
+    pub fn get_revision(&self, repo: &git2::Repository) -> Revision {
+        repo.config()
+            .and_then(|config| config.get_string(SPEC_REVISION_CONFIG))
+            .ok()
+            .and_then(|revision| revision.parse::<Revision>().ok())
+            .unwrap_or_default()
+    }
+
+    /// The mutator method `set_revision` remembers the revision
+    /// followed by a repository.
+    fn set_revision(&self,
+                    repo: &git2::Repository,
+                    revision: &Revision)
+                    -> Result<()> {
+        repo.config()
+            .and_then(|mut config|
+                config.set_str(SPEC_REVISION_CONFIG, &revision.to_string()))
+            .or_else(|why: git2::Error|
+                Err(CompositerError::UpdateRepConfig(why)))
+    }
+
+    /// The method `resolve` returns the object targeted by a revision,
+    /// the head is detached from the branch for a pinned revision.
+    fn resolve<'a>(&self,
+                   repo: &'a git2::Repository,
+                   revision: &Revision)
+                   -> Result<git2::Object<'a>> {
+        let target: Option<git2::Oid> = match *revision {
+            Revision::Head => {
+                match repo.head() {
+                    Err(why) => return Err(CompositerError::UpdateRepBranch(why)),
+                    Ok(head) => head.target(),
+                }
+            },
+            Revision::Branch(ref branch) => {
+                match repo.find_branch(branch, git2::BranchType::Local)
+                          .and_then(|found| repo.set_head(
+                              &format!("refs/heads/{}", branch)
+                          ).and(Ok(found.get().target()))) {
+                    Err(why) => return Err(CompositerError::UpdateRepBranch(why)),
+                    Ok(target) => target,
+                }
+            },
+            Revision::Tag(ref name) => {
+                match repo.revparse_single(&format!("refs/tags/{}", name))
+                          .and_then(|object| object.peel(git2::ObjectType::Commit)) {
+                    Err(why) => return Err(CompositerError::UpdateRepRevision(why)),
+                    Ok(commit) => Some(commit.id()),
+                }
+            },
+            Revision::Rev(ref rev) => {
+                match repo.revparse_single(rev)
+                          .and_then(|object| object.peel(git2::ObjectType::Commit)) {
+                    Err(why) => return Err(CompositerError::UpdateRepRevision(why)),
+                    Ok(commit) => Some(commit.id()),
+                }
+            },
+        };
+        match target {
+            None => Err(CompositerError::UpdateRepBranchId),
+            Some(id) => {
+                if revision.is_pinned() {
+                    if let Err(why) = repo.set_head_detached(id) {
+                        return Err(CompositerError::UpdateRepRevision(why));
+                    }
+                }
+                repo.find_object(id, None)
+                    .or_else(|why: git2::Error|
+                        Err(CompositerError::UpdateRepObject(why)))
+            },
+        }
+    }
+
+    /// The method `checkout_revision` remembers the revision followed
+    /// by a repository and hard-resets it to the revision's commit.
+    fn checkout_revision(&self,
+                         repo: &git2::Repository,
+                         revision: &Revision)
+                         -> Result<()> {
+        self.set_revision(repo, revision)
+            .and_then(|_| self.resolve(repo, revision))
+            .and_then(|object| self.reset(repo, &object))
+    }
+
+    /// The method `update` stages the last commit of the followed branch,
+    /// a library pinned to a tag or a commit stays pinned, and makes it
+    /// with its dependencies. The staged versions are swapped with the
+    /// installed ones only if they're built and loaded with success,
+    /// the previous versions are kept to the rollback sub-directory
+    /// unless the staged versions can't be remounted.
+    /// A local library is rebuilt from the disk.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn update(&mut self, libraryname: &str) -> Result<()> {
+        let dest: PathBuf = try!(self.get_git()).join(&libraryname);
+
+        if self.is_linked(&dest) {
+            return self.build(&dest, &libraryname);
+        }
+        let (url, revision): (String, Revision) = match git2::Repository::open(&dest) {
+            Err(why) => return Err(CompositerError::UpdateRepOpen(why)),
+            Ok(repo) => {
+                let url: String = try!(repo.find_remote("origin")
+                    .map_err(CompositerError::UpdateRepOrigin))
+                    .url()
+                    .unwrap_or_default()
+                    .to_string();
+
+                (url, self.get_revision(&repo))
+            },
+        };
+        let rollback: PathBuf = try!(self.get_rollback());
+        let staging: PathBuf = rollback.join(SPEC_SUBD_STAGING);
+
+        let _ = fs::remove_dir_all(&staging);
+        try!(fs::create_dir_all(&staging)
+                 .map_err(CompositerError::MkDirRollback));
+        let staged: Result<Vec<String>> =
+            self.clone_revision(&url, &staging.join(&libraryname), &revision)
+                .and_then(|repo| self.checkout_revision(&repo, &revision))
+                .and_then(|_| self.stage(&staging, &libraryname))
+                .and_then(|staged| {
+                    for sub in &staged {
+                        self.forget(sub);
+                        try!(fs::rename(staging.join(sub), rollback.join(sub))
+                                 .and_then(|_| fs::rename(staging.join(sub)
+                                                                 .with_extension(SPEC_LIB_EXT),
+                                                          rollback.join(sub)
+                                                                  .with_extension(SPEC_LIB_EXT)))
+                                 .map_err(CompositerError::RollbackSwap));
+                    }
+                    Ok(staged)
+                });
+
+        let _ = fs::remove_dir_all(&staging);
+        let staged: Vec<String> = try!(staged);
+
+        try!(self.swap_all(&staged));
+        let result: Result<()> = self.remount_all(&staged, &libraryname);
+
+        if result.is_err() {
+            try!(self.swap_all(&staged));
+            for sub in &staged {
+                self.forget(sub);
+            }
+            try!(self.remount_all(&staged, &libraryname));
+        }
+        self.reload_except(&staged);
+        result
+    }
+
+    /// The method `stage` makes a staged library and its dependencies
+    /// from the staging sub-directory without touching the installed
+    /// ones, and loads every dynamic library once to verify it.
+    /// The installed dependencies which already match are reused
+    /// and aren't staged. The library names are returned in
+    /// topological order.
+    /// @ staging: `$HOME/.neko/rollback/staging`.
+    /// @ libraryname: `arukana@libnya`.
+    fn stage(&self, staging: &PathBuf, libraryname: &str) -> Result<Vec<String>> {
+        let staged: Vec<String> = try!(self.resolve_dependencies(staging, libraryname))
+            .into_iter()
+            .filter(|sub| fs::symlink_metadata(staging.join(sub)).is_ok())
+            .collect::<Vec<String>>();
+
+        for sub in &staged {
+            let source: PathBuf = staging.join(sub);
+            let library: PathBuf = source.with_extension(SPEC_LIB_EXT);
+
+            if sub.ne(libraryname) {
+                try!(self.trust_dependency(&source, sub));
+            } else {
+                try!(self.verify(&source, sub));
+            }
+            try!(self.make(&source, sub)
+                     .and_then(|artifact| fs::rename(artifact, &library)
+                                             .map_err(CompositerError::MvFail))
+                     .and_then(|_| Library::get_hooks(&library)
+                                           .map_err(CompositerError::UpdateLoad)));
+        }
+        Ok(staged)
+    }
+
+    /// The method `remount_all` remounts and pins the swapped libraries,
+    /// a dependency which isn't installed anymore is unmounted.
+    /// @ swapped: `["arukana@libmeow", "arukana@libnya"]`.
+    /// @ libraryname: `arukana@libnya`.
+    fn remount_all(&mut self, swapped: &[String], libraryname: &str) -> Result<()> {
+        let git: PathBuf = try!(self.get_git());
+
+        for sub in swapped {
+            if fs::symlink_metadata(git.join(sub)).is_err() {
+                let _ = self.detach(sub);
+                try!(self.set_mounted(sub, None)
+                         .and_then(|_| self.unlock(sub)));
+            } else if sub.eq(libraryname) {
+                try!(self.remount(sub).and_then(|_| self.lock(sub)));
+            } else {
+                try!(self.remount(sub).and_then(|_| self.lock_dependency(sub)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The method `rollback` swaps a updated library with its previous
+    /// version, a second rollback restores the update.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn rollback(&mut self, libraryname: &str) -> Result<()> {
+        if try!(self.get_rollback()).join(libraryname).exists().not() {
+            Err(CompositerError::Rollback)
+        } else {
+            let result: Result<()> = self.swap(&libraryname)
+                                         .and_then(|_| self.remount(&libraryname))
+                                         .and_then(|_| self.lock(&libraryname));
+
+            self.reload_except(&[libraryname.to_string()]);
+            result
+        }
+    }
+
+    /// The method `swap_all` swaps the libraries with their staged
+    /// versions, the already swapped ones are swapped back if one fails.
+    /// @ libraries: `["arukana@libmeow", "arukana@libnya"]`.
+    fn swap_all(&self, libraries: &[String]) -> Result<()> {
+        for (index, libraryname) in libraries.iter().enumerate() {
+            if let Err(why) = self.swap(libraryname) {
+                for swapped in libraries[..index].iter().rev() {
+                    let _ = self.swap(swapped);
+                }
+                return Err(why);
+            }
+        }
+        Ok(())
+    }
+
+    /// The method `swap` exchanges the repository and the dynamic library
+    /// of a installed library with the ones of the rollback sub-directory.
+    /// @ libraryname: `arukana@libnya`.
+    fn swap(&self, libraryname: &str) -> Result<()> {
+        let (git, lib): (PathBuf, PathBuf) = try!(self.git_with_lib());
+        let rollback: PathBuf = try!(self.get_rollback());
+
+        swap::exchange(&git.join(libraryname), &rollback.join(libraryname))
+            .and_then(|_| swap::exchange(
+                &self.find_library(&lib, &libraryname),
+                &rollback.join(libraryname).with_extension(SPEC_LIB_EXT)
+            ))
+            .or_else(|why: io::Error| Err(CompositerError::RollbackSwap(why)))
+    }
+
+    /// The method `forget` removes the previous version
+    /// of a library from the rollback sub-directory.
+    /// @ libraryname: `arukana@libnya`.
+    fn forget(&self, libraryname: &str) {
+        if let Ok(rollback) = self.get_rollback() {
+            let _ = fs::remove_dir_all(rollback.join(libraryname));
+            let _ = fs::remove_file(rollback.join(libraryname)
+                                            .with_extension(SPEC_LIB_EXT));
+        }
+    }
+
+    /// The method `checkout` clones if needed and hard-resets
+    /// a repository to the commit pinned by the Neko.lock.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    fn checkout(&self, source: &PathBuf, locked: &Locked) -> Result<()> {
+        if let Source::Local(path) = try!(locked.get_source().parse::<Source>()) {
+            return if self.is_linked(source) {
+                Ok(())
+            } else {
+                self.link(&path, source)
+            };
+        }
+        let revision: Revision = try!(locked.get_revision().parse::<Revision>());
+        let repo: git2::Repository = if source.exists() {
+            try!(git2::Repository::open(source)
+                .map_err(CompositerError::UpdateRepOpen))
+        } else {
+            try!(self.clone_revision(locked.get_source(), source, &revision))
+        };
+        let id: git2::Oid = try!(git2::Oid::from_str(locked.get_commit())
+            .map_err(CompositerError::LockRepCommit));
+
+        if repo.find_commit(id).is_err() {
+            try!(self.fetch(&repo));
+        }
+        try!(self.set_revision(&repo, &revision));
+        if revision.is_pinned() {
+            try!(repo.set_head_detached(id)
+                .map_err(CompositerError::LockRepCommit));
+        }
+        match repo.find_object(id, None) {
+            Err(why) => Err(CompositerError::LockRepCommit(why)),
+            Ok(obj) => self.reset(&repo, &obj),
+        }
+    }
+
+    /// The method `install_locked` clones and makes every library
+    /// of the Neko.lock at exactly its pinned commit, the dependencies
+    /// before their dependents.
+    /// The make is skipped when the dynamic library already matches
+    /// the pinned checksum. A newly added library is installed without
+    /// granted capability until it's approved by `grant`.
+    pub fn install_locked(&mut self) -> Result<()> {
+        let lock: Lock = try!(self.get_lock());
+        let (git, lib): (PathBuf, PathBuf) = try!(self.git_with_lib());
+        let order: Vec<String> = try!(lock.get_graph().order_all());
+        let mut added: Vec<String> = Vec::new();
+        let mut result: Result<()> = Ok(());
+
+        for libraryname in &order {
+            if let Some(locked) = lock.get(libraryname) {
+                let source: PathBuf = git.join(libraryname);
+
+                if fs::symlink_metadata(&source).is_err() {
+                    added.push(libraryname.clone());
+                }
+                result = self.set_ungranted(libraryname)
+                             .and_then(|_| self.checkout(&source, locked))
+                             .and_then(|_| if self.checksum(&self.find_library(&lib, libraryname))
+                                                  .ok()
+                                                  .map_or(true, |checksum|
+                                                      checksum.ne(locked.get_checksum())) {
+                                 self.verify(&source, libraryname)
+                                     .and_then(|_| self.compile(&source, libraryname))
+                             } else {
+                                 Ok(())
+                             })
+                             .and_then(|_| self.remount(libraryname));
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        self.reload_except(&order);
+        self.installed(&added);
+        result
+    }
+
+    /// The method `bundle` packs the repositories of a pinned library and
+    /// of its dependencies with their Neko.lock to a archive, by default
+    /// `<libraryname>.tar` from the Neko root. A library built by cargo
+    /// is refused because it couldn't be built without network.
+    /// @ libraryname: `arukana@libnya`.
+    /// @ dest: `arukana@libnya.tar`.
+    pub fn bundle<S: AsRef<OsStr>, P: AsRef<Path>>(&self,
+                                                   libraryname: &S,
+                                                   dest: Option<P>)
+                                                   -> Result<PathBuf> {
+        let libraryname: &str = libraryname.as_ref().to_str().unwrap_or_default();
+        let git: PathBuf = try!(self.get_git());
+        let lock: Lock = try!(self.get_lock());
+        let dest: PathBuf = dest.map(|dest| dest.as_ref().to_path_buf())
+                                .unwrap_or_else(|| self.get_root()
+                                                       .join(libraryname)
+                                                       .with_extension(SPEC_BUNDLE_EXT));
+        let mut bundled: Lock = Lock::default();
+
+        for dependency in try!(lock.get_graph().order(libraryname)) {
+            try!(self.offline(&git.join(&dependency), &dependency));
+            match lock.get(&dependency) {
+                None => return Err(CompositerError::BundleLocked(dependency)),
+                Some(locked) => bundled.insert(dependency.clone(), locked.clone()),
+            }
+        }
+        bundle::pack(&dest, &git, &bundled).and(Ok(dest))
+    }
+
+    /// The method `offline` refuses a library of a bundle which
+    /// is built by cargo, because cargo downloads the crates.
+    /// @ source: `$HOME/.neko/git/arukana@libnya`.
+    /// @ libraryname: `arukana@libnya`.
+    fn offline(&self, source: &PathBuf, libraryname: &str) -> Result<()> {
+        match try!(self.get_build(source)).get_backend() {
+            &Backend::Cargo => Err(CompositerError::BundleCargo(libraryname.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// The method `install_bundle` unpacks a archive from `bundle` and
+    /// makes its libraries without network, the dependencies before
+    /// their dependents. A already installed library is kept, and
+    /// a library built by cargo is refused before anything is moved.
+    /// A newly added library is installed without granted capability
+    /// until it's approved by `grant`.
+    /// @ path: `arukana@libnya.tar`.
+    pub fn install_bundle<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let git: PathBuf = try!(self.get_git());
+        let unpacked: PathBuf = self.get_root().join(SPEC_SUBD_BUNDLE);
+        let _ = fs::remove_dir_all(&unpacked);
+        let bundled: Lock = try!(bundle::unpack(path.as_ref(), &unpacked));
+        let graph: Graph = bundled.get_graph();
+        let order: Vec<String> = try!(graph.order_all());
+
+        for libraryname in &order {
+            let source: PathBuf = git.join(libraryname);
+
+            if fs::symlink_metadata(&source).is_ok() {
+                try!(self.offline(&source, libraryname));
+            } else {
+                try!(self.offline(&unpacked.join(SPEC_SUBD_GIT).join(libraryname), libraryname));
+            }
+        }
+        let mut added: Vec<String> = Vec::new();
+        let mut result: Result<()> = Ok(());
+
+        for libraryname in &order {
+            let source: PathBuf = git.join(libraryname);
+
+            if fs::symlink_metadata(&source).is_err() {
+                added.push(libraryname.clone());
+                result = self.set_ungranted(libraryname).and_then(|_|
+                    fs::rename(unpacked.join(SPEC_SUBD_GIT).join(libraryname), &source)
+                       .map_err(CompositerError::BundleRead));
+            }
+            result = result.and_then(|_| self.verify(&source, libraryname))
+                           .and_then(|_| self.compile(&source, libraryname))
+                           .and_then(|_| self.remount(libraryname))
+                           .and_then(|_| if graph.get_dependents(libraryname).is_empty() {
+                               self.lock(libraryname)
+                           } else {
+                               self.lock_dependency(libraryname)
+                           });
+            if result.is_err() {
+                break;
+            }
+        }
+        self.reload_except(&order);
+        self.installed(&added);
+        try!(result);
+        fs::remove_dir_all(&unpacked).or_else(|why: io::Error|
+            Err(CompositerError::BundleRead(why)))
+    }
+
+    /// The method `uninstall` removes library from the filesystem with
+    /// the source, unless other installed libraries depend on it.
+    /// @libraryname: `arukana@libnya`.
+    pub fn uninstall<S: AsRef<OsStr>>(&mut self,
+                                      libraryname: &S)
+                                      -> Result<()> {
+        let name: &str = libraryname.as_ref().to_str().unwrap_or_default();
+        let dependents: Vec<String> = try!(self.get_lock()).get_graph()
+                                                           .get_dependents(name);
+
+        if dependents.is_empty() {
+            self.uninstall_forced(libraryname)
+        } else {
+            Err(CompositerError::Dependents(name.to_string(), dependents))
+        }
+    }
+
+    /// The method `autoremove` uninstalls the libraries installed only
+    /// as dependencies which aren't required anymore, and returns
+    /// their names.
+    pub fn autoremove(&mut self) -> Result<Vec<String>> {
+        let mut removed: Vec<String> = Vec::new();
+
+        loop {
+            let lock: Lock = try!(self.get_lock());
+            let graph: Graph = lock.get_graph();
+            let unrequired: Vec<String> = lock.into_iter()
+                .filter(|&(libraryname, locked)|
+                    locked.is_dependency() &&
+                    graph.get_dependents(libraryname).is_empty())
+                .map(|(libraryname, _)| libraryname.clone())
+                .collect::<Vec<String>>();
+
+            if unrequired.is_empty() {
+                return Ok(removed);
+            }
+            for libraryname in unrequired {
+                try!(self.uninstall_forced(&libraryname));
+                removed.push(libraryname);
+            }
+        }
+    }
+
+    /// The method `uninstall_forced` calls the hook `uninstall` of
+    /// the library if it's mounted, then removes it from the filesystem
+    /// with the source, even if other installed libraries depend on it.
+    /// @libraryname: `arukana@libnya`.
+    pub fn uninstall_forced<S: AsRef<OsStr>>(&mut self,
+                                             libraryname: &S)
+                                             -> Result<()> {
+        if let Some(dy) = self.list.iter().find(|s| {
+            s.as_path_buf().file_stem().eq(&Some(libraryname.as_ref()))
+        }) {
+            dy.uninstall(&mut self.state);
+        }
+        match self.detach(libraryname) {
             Ok(_) |
             Err(CompositerError::UnmountPosition) => {
                 let path: PathBuf = PathBuf::from(libraryname);
                 match (self.get_git(), self.get_lib()) {
                     (Ok(git), Ok(lib)) => {
-                        if let Err(why) = fs::remove_file(lib.join(&path)
-                            .with_extension(SPEC_LIB_EXT)) {
+                        let library: PathBuf = self.find_library(&lib, &path);
+
+                        if let Err(why) = fs::remove_file(&library) {
                             Err(CompositerError::RmFile(why))
                         } else if let Err(why) =
                             fs::remove_dir_all(git.join(&path)) {
                             Err(CompositerError::RmDir(why))
                         } else {
-                            Ok(())
+                            let _ = fs::remove_file(
+                                library.with_extension(SPEC_LIB_EXT_LEGACY)
+                            );
+                            self.forget(path.to_str().unwrap_or_default());
+                            self.unlock(libraryname)
+                                .and_then(|_| self.set_mounted(libraryname, None))
                         }
                     }
                     (Err(why), _) | (_, Err(why)) => Err(why),
@@ -411,25 +2225,62 @@ impl Compositer {
     /// The general method `call` according to the state will run
     /// the evenement functions by library group.
     pub fn resized(&mut self, size: &pty::Winszed) {
+        let state: &mut LibraryState = &mut self.state;
+
         self.list.iter()
             .all(|lib: &Library| {
-                lib.resized(&self.state, size);
+                lib.call_resized(state, size);
                 true
             });
-        self.list.retain(|lib: &Library|
-             lib.is_unmounted().not());
+        self.unmount_requested();
     }
 
     /// The general method `call` according to the state will run
     /// the evenement functions by library group.
     pub fn call(&mut self, event: &ShellState) {
+        self.hot_reload();
+        self.poll_install();
+        let state: &mut LibraryState = &mut self.state;
+
         self.list.iter()
             .all(|lib: &Library| {
-                lib.call(&self.state, event);
+                lib.call(state, event);
                 true
             });
-        self.list.retain(|lib: &Library|
-             lib.is_unmounted().not());
+        self.unmount_requested();
+    }
+
+    /// The method `call_with_screen` runs the evenement functions like
+    /// `call` while the libraries can read the screen.
+    pub fn call_with_screen(&mut self,
+                            event: &ShellState,
+                            screen: &pty::Display,
+                            size: &pty::Winszed) {
+        let host: Rc<Host> = self.host.clone();
+
+        host.with_screen(screen, size, || self.call(event));
+    }
+
+    /// The method `take_input` returns the bytes written by
+    /// the libraries to the pty since the last call.
+    pub fn take_input(&self) -> Vec<u8> {
+        self.host.take_input()
+    }
+
+    /// The method `unmount_requested` detaches the libraries which
+    /// requested it by their host's table, for this session only:
+    /// their state of the Neko.mount is kept.
+    fn unmount_requested(&mut self) {
+        let requested: Vec<PathBuf> = self.list.iter()
+            .filter(|lib: &&Library| lib.is_unmounted())
+            .map(|lib: &Library| lib.as_path_buf().clone())
+            .collect::<Vec<PathBuf>>();
+
+        for path in requested {
+            if let Some(libraryname) = path.file_stem() {
+                let _ = self.detach(libraryname);
+            }
+        }
     }
 }
 
@@ -442,13 +2293,37 @@ impl<'a> IntoIterator for &'a Compositer {
     }
 }
 
+impl Drop for Compositer {
+    /// The destructor `drop` ends every mounted library,
+    /// which frees its userdata before being closed.
+    fn drop(&mut self) {
+        for lib in self.list.drain(..) {
+            lib.end(&mut self.state);
+        }
+    }
+}
+
 /// A trait for giving a type a useful default value.
 impl Default for Compositer {
-    /// The constructor `default` returns a empty Compositer.
+    /// The constructor `default` returns a empty Compositer
+    /// with the root from the $NEKO_PATH variable.
     fn default() -> Compositer {
         Compositer {
+            root: env::var(SPEC_ROOT).ok()
+                .and_then(|repertory: String|
+                          Some(PathBuf::from(repertory)))
+                .unwrap_or_else(||
+                          PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                                            .join(SPEC_ROOT_DEFAULT)),
             list: Vec::with_capacity(SPEC_CAPACITY),
             state: LibraryState::default(),
+            watch: None,
+            installing: None,
+            approved: Capabilities::default(),
+            untrusted: None,
+            legacy: Vec::new(),
+            progress: None,
+            host: Rc::new(Host::default()),
         }
     }
 }
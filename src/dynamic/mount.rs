@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use ::toml;
+
+use super::capability::Capabilities;
+use super::err::{CompositerError, Result};
+
+/// The name of enabled label.
+const SPEC_MOUNT_ENABLED: &'static str = "enabled";
+/// The name of priority label.
+const SPEC_MOUNT_PRIORITY: &'static str = "priority";
+/// The name of granted capabilities label.
+const SPEC_MOUNT_CAPABILITIES: &'static str = "capabilities";
+
+/// The struct `Mounted` is the persisted mount state of a library.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mounted {
+    /// The library is mounted at startup.
+    enabled: bool,
+    /// The priority given by `neko mount`, else the manifest's priority.
+    priority: Option<i64>,
+    /// The capabilities granted by the user, or none for a library
+    /// mounted before the capabilities which is asked once to be granted.
+    capabilities: Option<Capabilities>,
+}
+
+impl Mounted {
+    /// The constructor method `new` returns a mount state
+    /// whose capabilities were never granted.
+    pub fn new(enabled: bool, priority: Option<i64>) -> Self {
+        Mounted {
+            enabled: enabled,
+            priority: priority,
+            capabilities: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_priority(&self) -> Option<i64> {
+        self.priority
+    }
+
+    pub fn get_capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// The mutator method `set_capabilities` replaces
+    /// the granted capabilities.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = Some(capabilities);
+    }
+
+    /// The method `get_given` returns the capabilities given to the library
+    /// of a manifest: the requested capabilities which are granted, or none
+    /// while the library was never granted.
+    /// @ requested: `input,persona`.
+    pub fn get_given(&self, requested: &Capabilities) -> Capabilities {
+        self.capabilities.map_or_else(Capabilities::default, |granted|
+            requested.intersection(&granted)
+        )
+    }
+
+    /// The constructor method `from_table` returns a mount state
+    /// from a Neko.mount's table.
+    fn from_table(table: &toml::Table) -> Option<Self> {
+        let capabilities: Option<Option<Capabilities>> = match table.get(SPEC_MOUNT_CAPABILITIES) {
+            None => Some(None),
+            Some(value) => value.as_slice()
+                                .and_then(|capabilities|
+                                    Capabilities::from_slice(capabilities).ok())
+                                .map(Some),
+        };
+
+        table.get(SPEC_MOUNT_ENABLED)
+             .and_then(|value| value.as_bool())
+             .and_then(|enabled| capabilities.map(|capabilities| Mounted {
+                 enabled: enabled,
+                 priority: table.get(SPEC_MOUNT_PRIORITY)
+                                .and_then(|value| value.as_integer()),
+                 capabilities: capabilities,
+             }))
+    }
+
+    /// The method `to_table` returns the Neko.mount's table of
+    /// the mount state.
+    fn to_table(&self) -> toml::Table {
+        let mut table: toml::Table = toml::Table::new();
+
+        table.insert(SPEC_MOUNT_ENABLED.to_string(),
+                     toml::Value::Boolean(self.enabled));
+        if let Some(priority) = self.priority {
+            table.insert(SPEC_MOUNT_PRIORITY.to_string(),
+                         toml::Value::Integer(priority));
+        }
+        if let Some(capabilities) = self.capabilities {
+            table.insert(SPEC_MOUNT_CAPABILITIES.to_string(),
+                         toml::Value::Array(capabilities.to_slice()));
+        }
+        table
+    }
+}
+
+/// The struct `Mount` is the Neko.mount dictionary of
+/// library names and their mount states.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mount {
+    libraries: BTreeMap<String, Mounted>,
+}
+
+impl Mount {
+    /// The constructor method `from_path` returns the Neko.mount
+    /// or a empty Mount if the file doesn't exist.
+    /// @ path: `$HOME/.neko/Neko.mount`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match File::open(path) {
+            Err(ref why) if why.kind().eq(&io::ErrorKind::NotFound) => {
+                Ok(Mount::default())
+            },
+            Err(why) => Err(CompositerError::ReadMount(why)),
+            Ok(mut descripter) => {
+                let mut buffer = String::new();
+                match descripter.read_to_string(&mut buffer) {
+                    Err(why) => Err(CompositerError::ReadMount(why)),
+                    Ok(_) => buffer.parse::<Mount>(),
+                }
+            }
+        }
+    }
+
+    /// The method `save` writes the Neko.mount.
+    /// @ path: `$HOME/.neko/Neko.mount`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        File::create(path).and_then(|mut descripter|
+            descripter.write_all(self.to_string().as_bytes())
+        ).or_else(|why: io::Error| Err(CompositerError::WriteMount(why)))
+    }
+
+    /// The accessor method `get` returns the mount state of a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn get(&self, libraryname: &str) -> Option<&Mounted> {
+        self.libraries.get(libraryname)
+    }
+
+    /// The method `insert` records the mount state of a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn insert(&mut self, libraryname: String, mounted: Mounted) {
+        self.libraries.insert(libraryname, mounted);
+    }
+
+    /// The method `remove` forgets the mount state of a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn remove(&mut self, libraryname: &str) -> Option<Mounted> {
+        self.libraries.remove(libraryname)
+    }
+}
+
+impl FromStr for Mount {
+    type Err = CompositerError;
+
+    fn from_str(buffer: &str) -> Result<Self> {
+        if let Some(table) = toml::Parser::new(buffer).parse() {
+            table.iter()
+                 .map(|(libraryname, value)|
+                     value.as_table()
+                          .and_then(|table| Mounted::from_table(table))
+                          .and_then(|mounted| Some((libraryname.clone(), mounted)))
+                          .ok_or(CompositerError::ParseMount))
+                 .collect::<Result<BTreeMap<String, Mounted>>>()
+                 .and_then(|libraries| Ok(Mount { libraries: libraries }))
+        } else {
+            Err(CompositerError::ParseMount)
+        }
+    }
+}
+
+impl ToString for Mount {
+    fn to_string(&self) -> String {
+        toml::Value::Table(
+            self.libraries.iter()
+                .map(|(libraryname, mounted)|
+                    (libraryname.clone(), toml::Value::Table(mounted.to_table())))
+                .collect::<toml::Table>()
+        ).to_string()
+    }
+}
+
+impl<'a> IntoIterator for &'a Mount {
+    type Item = (&'a String, &'a Mounted);
+    type IntoIter = ::std::collections::btree_map::Iter<'a, String, Mounted>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.libraries.iter()
+    }
+}
@@ -0,0 +1,183 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use super::err::Result;
+use super::lock::{Lock, Locked};
+use super::mount::{Mount, Mounted};
+use super::trust::Trust;
+
+/// The enum `Progress` is a message from a installation
+/// which runs in background, its changes of the Neko.mount,
+/// the Neko.lock and the Neko.trust are written by the main thread.
+#[derive(Debug)]
+pub enum Progress {
+    /// A line of progress like `Receiving objects: 45% (123/270)`.
+    Line(String),
+    /// The mount state of a library to record to the Neko.mount, or to forget.
+    Mount(String, Option<Mounted>),
+    /// The pin of a library to record to the Neko.lock, or to forget.
+    Lock(String, Option<Locked>),
+    /// The account to confirm to the Neko.trust like `github.com/arukana`.
+    Trust(String),
+    /// The key to confirm to the Neko.trust like
+    /// `D8692123C4065DEA5E0F3AB5249B39D24F25E3B6`.
+    Fingerprint(String),
+    /// The host to confirm to the Neko.trust like `github.com`.
+    Host(String),
+    /// The installation of a repository has ended with the name
+    /// of its library and the names of the libraries it pinned.
+    End(String, Result<String>, Vec<String>),
+}
+
+/// The struct `Reporter` sends the messages of a installation which
+/// runs in background, and keeps the changes of the Neko.mount,
+/// the Neko.lock and the Neko.trust sent to the main thread which
+/// may be not yet written.
+#[derive(Debug)]
+pub struct Reporter {
+    sender: mpsc::Sender<Progress>,
+    /// The mount states by library name, none for a forgotten library.
+    mount: RefCell<BTreeMap<String, Option<Mounted>>>,
+    /// The pins by library name, none for a forgotten library.
+    lock: RefCell<BTreeMap<String, Option<Locked>>>,
+    /// The confirmed accounts like `github.com/arukana`.
+    trust: RefCell<Vec<String>>,
+    /// The confirmed keys.
+    fingerprints: RefCell<Vec<String>>,
+    /// The confirmed hosts like `github.com`.
+    hosts: RefCell<Vec<String>>,
+}
+
+impl Reporter {
+    /// The constructor method `new` returns a reporter without change.
+    pub fn new(sender: mpsc::Sender<Progress>) -> Self {
+        Reporter {
+            sender: sender,
+            mount: RefCell::new(BTreeMap::new()),
+            lock: RefCell::new(BTreeMap::new()),
+            trust: RefCell::new(Vec::new()),
+            fingerprints: RefCell::new(Vec::new()),
+            hosts: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// The method `send` sends a message to the main thread
+    /// and keeps its change.
+    pub fn send(&self, progress: Progress) {
+        match progress {
+            Progress::Mount(ref libraryname, mounted) => {
+                self.mount.borrow_mut().insert(libraryname.clone(), mounted);
+            },
+            Progress::Lock(ref libraryname, ref locked) => {
+                self.lock.borrow_mut().insert(libraryname.clone(), locked.clone());
+            },
+            Progress::Trust(ref account) => {
+                self.trust.borrow_mut().push(account.clone());
+            },
+            Progress::Fingerprint(ref fingerprint) => {
+                self.fingerprints.borrow_mut().push(fingerprint.clone());
+            },
+            Progress::Host(ref host) => {
+                self.hosts.borrow_mut().push(host.clone());
+            },
+            Progress::Line(_) | Progress::End(..) => {},
+        }
+        let _ = self.sender.send(progress);
+    }
+
+    /// The method `get_locked` returns the names of the sent pins.
+    pub fn get_locked(&self) -> Vec<String> {
+        self.lock.borrow()
+                 .iter()
+                 .filter(|&(_, locked)| locked.is_some())
+                 .map(|(libraryname, _)| libraryname.clone())
+                 .collect::<Vec<String>>()
+    }
+
+    /// The method `apply_mount` replays the sent mount states to the Neko.mount.
+    pub fn apply_mount(&self, mount: &mut Mount) {
+        for (libraryname, mounted) in self.mount.borrow().iter() {
+            if let Some(mounted) = *mounted {
+                mount.insert(libraryname.clone(), mounted);
+            } else {
+                mount.remove(libraryname);
+            }
+        }
+    }
+
+    /// The method `apply_lock` replays the sent pins to the Neko.lock.
+    pub fn apply_lock(&self, lock: &mut Lock) {
+        for (libraryname, locked) in self.lock.borrow().iter() {
+            if let Some(ref locked) = *locked {
+                lock.insert(libraryname.clone(), locked.clone());
+            } else {
+                lock.remove(libraryname);
+            }
+        }
+    }
+
+    /// The method `apply_trust` replays the sent accounts, keys and hosts
+    /// to the Neko.trust.
+    pub fn apply_trust(&self, trust: &mut Trust) {
+        for account in self.trust.borrow().iter() {
+            trust.insert_account(account.to_lowercase());
+        }
+        for fingerprint in self.fingerprints.borrow().iter() {
+            trust.insert_fingerprint(fingerprint);
+        }
+        for host in self.hosts.borrow().iter() {
+            trust.insert_host(host.to_lowercase());
+        }
+    }
+}
+
+/// The function `run` runs a command, writes its output and its errors
+/// to the log and reports each of their lines as soon as it's printed.
+/// @ command: `make` from `$HOME/.neko/git/arukana@libnya`.
+/// @ log: `$HOME/.neko/log/arukana@libnya.log`.
+pub fn run<W: Write, F: FnMut(&str)>(command: &mut Command,
+                                      log: &mut W,
+                                      mut report: F)
+                                      -> io::Result<ExitStatus> {
+    let mut child = try!(command.stdin(Stdio::null())
+                                .stdout(Stdio::piped())
+                                .stderr(Stdio::piped())
+                                .spawn());
+    let (sender, receiver): (mpsc::Sender<String>, mpsc::Receiver<String>) =
+        mpsc::channel();
+    let outputs: Vec<Box<Read + Send>> = vec![
+        child.stdout.take().map(|output| Box::new(output) as Box<Read + Send>),
+        child.stderr.take().map(|output| Box::new(output) as Box<Read + Send>),
+    ].into_iter()
+     .filter_map(|output| output)
+     .collect::<Vec<Box<Read + Send>>>();
+    let readers: Vec<thread::JoinHandle<()>> = outputs.into_iter().map(|output| {
+        let sender: mpsc::Sender<String> = sender.clone();
+
+        thread::spawn(move || {
+            for line in BufReader::new(output).lines().filter_map(|line| line.ok()) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+    }).collect::<Vec<thread::JoinHandle<()>>>();
+
+    drop(sender);
+    for line in receiver.iter() {
+        if let Err(why) = writeln!(log, "{}", line) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(why);
+        }
+        report(&line);
+    }
+    for reader in readers {
+        let _ = reader.join();
+    }
+    child.wait()
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use ::toml;
+
+use super::err::{CompositerError, Result};
+
+/// The extention of a registry's entry.
+const SPEC_REGISTRY_EXT: &'static str = "toml";
+/// The name of name label.
+const SPEC_REGISTRY_NAME: &'static str = "name";
+/// The name of description label.
+const SPEC_REGISTRY_DESCRIPTION: &'static str = "description";
+/// The name of url label.
+const SPEC_REGISTRY_URL: &'static str = "url";
+/// The name of versions label.
+const SPEC_REGISTRY_VERSIONS: &'static str = "versions";
+
+/// The struct `Entry` is a library published to the registry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// The short name like `libnya`.
+    name: String,
+    /// The description of the library.
+    description: String,
+    /// The repository like `https://github.com/Arukana/libnya.git`.
+    url: String,
+    /// The published versions like `v0.3.1`.
+    versions: Vec<String>,
+}
+
+impl Entry {
+    /// The constructor method `new` returns a published library.
+    pub fn new(name: String,
+               description: String,
+               url: String,
+               versions: Vec<String>)
+               -> Self {
+        Entry {
+            name: name,
+            description: description,
+            url: url,
+            versions: versions,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn get_versions(&self) -> &[String] {
+        &self.versions
+    }
+
+    /// The method `matches` returns true if the name or
+    /// the description contains the term, whatever the case.
+    /// @ term: `nya`.
+    pub fn matches(&self, term: &str) -> bool {
+        let term: String = term.to_lowercase();
+
+        self.name.to_lowercase().contains(&term) ||
+        self.description.to_lowercase().contains(&term)
+    }
+}
+
+impl FromStr for Entry {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses a entry like
+    /// `name = "libnya"`, `description = "..."`,
+    /// `url = "https://github.com/Arukana/libnya.git"`
+    /// and `versions = ["v0.3.1"]`.
+    fn from_str(buffer: &str) -> Result<Self> {
+        toml::Parser::new(buffer).parse().and_then(|table|
+            match (table.get(SPEC_REGISTRY_NAME).and_then(|value| value.as_str()),
+                   table.get(SPEC_REGISTRY_URL).and_then(|value| value.as_str())) {
+                (Some(name), Some(url)) => Some(Entry::new(
+                    name.to_string(),
+                    table.get(SPEC_REGISTRY_DESCRIPTION)
+                         .and_then(|value| value.as_str())
+                         .unwrap_or_default()
+                         .to_string(),
+                    url.to_string(),
+                    table.get(SPEC_REGISTRY_VERSIONS)
+                         .and_then(|value| value.as_slice())
+                         .unwrap_or_default()
+                         .iter()
+                         .filter_map(|version| version.as_str())
+                         .map(|version| version.to_string())
+                         .collect::<Vec<String>>(),
+                )),
+                _ => None,
+            }
+        ).ok_or(CompositerError::ParseRegistry)
+    }
+}
+
+/// The struct `Registry` is a dictionary of short names
+/// and their published libraries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Registry {
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Registry {
+    /// The constructor method `from_path` returns the registry
+    /// from every `.toml` entry of a directory.
+    /// @ path: `$HOME/.neko/registry`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let mut registry: Registry = Registry::default();
+
+        match fs::read_dir(path) {
+            Err(why) => Err(CompositerError::ReadRegistry(why)),
+            Ok(entries) => {
+                for path in entries.filter_map(|entry| entry.ok())
+                                   .map(|entry| entry.path())
+                                   .filter(|path| path.extension()
+                                                      .map_or(false, |extension|
+                                                          extension.eq(SPEC_REGISTRY_EXT))) {
+                    let mut buffer: String = String::new();
+
+                    try!(File::open(&path)
+                             .and_then(|mut descripter| descripter.read_to_string(&mut buffer))
+                             .map_err(CompositerError::ReadRegistry));
+                    registry.insert(try!(buffer.parse::<Entry>()));
+                }
+                Ok(registry)
+            },
+        }
+    }
+
+    /// The accessor method `get` returns the published library
+    /// of a short name.
+    /// @ name: `libnya`.
+    pub fn get(&self, name: &str) -> Option<&Entry> {
+        self.entries.get(name)
+    }
+
+    /// The method `insert` publishes a library.
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.insert(entry.name.clone(), entry);
+    }
+
+    /// The method `search` returns the published libraries
+    /// whose the name or the description contains the term.
+    /// @ term: `nya`.
+    pub fn search(&self, term: &str) -> Vec<&Entry> {
+        self.entries.values()
+                    .filter(|entry| entry.matches(term))
+                    .collect::<Vec<&Entry>>()
+    }
+}
+
+impl<'a> IntoIterator for &'a Registry {
+    type Item = (&'a String, &'a Entry);
+    type IntoIter = ::std::collections::btree_map::Iter<'a, String, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
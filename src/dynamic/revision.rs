@@ -0,0 +1,98 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::err::{CompositerError, Result};
+
+/// The separator between a repository and its revision.
+const SPEC_REVISION_SEPARATOR: char = '#';
+/// The name of branch label.
+const SPEC_REVISION_BRANCH: &'static str = "branch";
+/// The name of tag label.
+const SPEC_REVISION_TAG: &'static str = "tag";
+/// The name of rev label.
+const SPEC_REVISION_REV: &'static str = "rev";
+
+/// The enum `Revision` is the reference followed by a installed
+/// repository.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Revision {
+    /// The default branch of the origin like `master` or `main`.
+    Head,
+    /// A branch followed by update like `#branch=dev`.
+    Branch(String),
+    /// A tag pinned by update like `#v1.2.0` or `#tag=v1.2.0`.
+    Tag(String),
+    /// A commit pinned by update like `#rev=abc123`.
+    Rev(String),
+}
+
+impl Revision {
+    /// The constructor method `from_source` splits a source
+    /// in a repository and a revision.
+    /// @ source: `https://github.com/Arukana/libnya.git#branch=dev`.
+    pub fn from_source(source: &str) -> Result<(&str, Revision)> {
+        match source.rfind(SPEC_REVISION_SEPARATOR) {
+            None => Ok((source, Revision::Head)),
+            Some(index) => {
+                let (repository, revision): (&str, &str) = source.split_at(index);
+
+                revision[1..].parse::<Revision>()
+                             .and_then(|revision| Ok((repository, revision)))
+            }
+        }
+    }
+
+    /// The method `is_pinned` returns true when the revision
+    /// doesn't follow a branch.
+    pub fn is_pinned(&self) -> bool {
+        match *self {
+            Revision::Tag(_) | Revision::Rev(_) => true,
+            Revision::Head | Revision::Branch(_) => false,
+        }
+    }
+}
+
+impl FromStr for Revision {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses a revision like `v1.2.0`,
+    /// `tag=v1.2.0`, `branch=dev`, `rev=abc123` or a empty string
+    /// for the default branch.
+    fn from_str(revision: &str) -> Result<Self> {
+        match revision.find('=') {
+            None if revision.is_empty() => Ok(Revision::Head),
+            None => Ok(Revision::Tag(revision.to_string())),
+            Some(index) => {
+                let (label, name): (&str, &str) = revision.split_at(index);
+                let name: &str = &name[1..];
+
+                match label {
+                    _ if name.is_empty() => Err(CompositerError::InstallFormat),
+                    SPEC_REVISION_BRANCH => Ok(Revision::Branch(name.to_string())),
+                    SPEC_REVISION_TAG => Ok(Revision::Tag(name.to_string())),
+                    SPEC_REVISION_REV => Ok(Revision::Rev(name.to_string())),
+                    _ => Err(CompositerError::InstallFormat),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Revision {
+    /// The function `fmt` formats the revision like
+    /// the fragment of a source.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Revision::Head => Ok(()),
+            Revision::Branch(ref name) => write!(f, "{}={}", SPEC_REVISION_BRANCH, name),
+            Revision::Tag(ref name) => write!(f, "{}={}", SPEC_REVISION_TAG, name),
+            Revision::Rev(ref name) => write!(f, "{}={}", SPEC_REVISION_REV, name),
+        }
+    }
+}
+
+impl Default for Revision {
+    fn default() -> Revision {
+        Revision::Head
+    }
+}
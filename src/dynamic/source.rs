@@ -0,0 +1,105 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::err::{CompositerError, Result};
+use super::revision::Revision;
+
+/// The scheme of a local source.
+const SPEC_SOURCE_FILE: &'static str = "file://";
+/// The account of a local library.
+const SPEC_SOURCE_LOCAL: &'static str = "local";
+
+/// The enum `Source` is the origin of a installed library.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Source {
+    /// A remote repository like `https://github.com/Arukana/libnya.git`
+    /// and its revision.
+    Git(String, Revision),
+    /// A local directory like `./libnya` or `file:///home/neko/libnya`
+    /// which is linked to the git sub-directory.
+    Local(PathBuf),
+}
+
+impl Source {
+    /// The accessor method `get_libraryname` returns the library name
+    /// like `arukana@libnya` for a repository or `local@libnya`
+    /// for a local directory.
+    pub fn get_libraryname(&self) -> Result<String> {
+        match *self {
+            Source::Git(ref repo, _) => {
+                account_at_rep!(repo).ok_or(CompositerError::InstallFormat)
+            },
+            Source::Local(ref path) => {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.to_string())
+                    .or_else(|| fs::canonicalize(path).ok().and_then(|path|
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .map(|name| name.to_string())))
+                    .map(|name| format!("{}@{}", SPEC_SOURCE_LOCAL,
+                                        name.to_lowercase()))
+                    .ok_or(CompositerError::InstallFormat)
+            },
+        }
+    }
+
+    /// The method `is_local` returns true for a local directory.
+    pub fn is_local(&self) -> bool {
+        match *self {
+            Source::Local(_) => true,
+            Source::Git(..) => false,
+        }
+    }
+
+    /// The method `rebase` returns the source with a relative local
+    /// directory joined to the directory of its dependent.
+    /// @ base: `$HOME/.neko/git/arukana@libnya`.
+    pub fn rebase(self, base: &Path) -> Source {
+        match self {
+            Source::Local(ref path) if path.is_relative() => {
+                Source::Local(base.join(path))
+            },
+            source => source,
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses a source like
+    /// `https://github.com/Arukana/libnya.git#branch=dev`,
+    /// `file:///home/neko/libnya`, `/home/neko/libnya`
+    /// or `./libnya`.
+    fn from_str(source: &str) -> Result<Self> {
+        if source.starts_with(SPEC_SOURCE_FILE) {
+            Ok(Source::Local(PathBuf::from(&source[SPEC_SOURCE_FILE.len()..])))
+        } else if source.starts_with('/') ||
+                  source.starts_with("./") ||
+                  source.starts_with("../") ||
+                  source.eq(".") || source.eq("..") {
+            Ok(Source::Local(PathBuf::from(source)))
+        } else {
+            Revision::from_source(source).and_then(|(repo, revision)|
+                Ok(Source::Git(repo.to_string(), revision)))
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    /// The function `fmt` formats the source like
+    /// `https://github.com/Arukana/libnya.git#branch=dev`
+    /// or `file:///home/neko/libnya`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Source::Git(ref repo, Revision::Head) => write!(f, "{}", repo),
+            Source::Git(ref repo, ref revision) => write!(f, "{}#{}", repo, revision),
+            Source::Local(ref path) => {
+                write!(f, "{}{}", SPEC_SOURCE_FILE, path.display())
+            },
+        }
+    }
+}
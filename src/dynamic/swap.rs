@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ::libc;
+
+/// The extension of a file or directory moved aside during a exchange.
+pub const SPEC_SWAP_EXT: &'static str = "swap";
+/// The flag of `renameat2` which exchanges two paths at once.
+#[cfg(target_os = "linux")]
+const SPEC_RENAME_EXCHANGE: libc::c_uint = 1 << 1;
+
+/// The function `exists` returns true if a file, a directory
+/// or a link is at the path.
+fn exists(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+/// The function `exchange` renames two files or directories
+/// with each other, or moves the only one which exists.
+/// The kernel exchanges them at once when it's supported, else
+/// the left one is moved aside to its swap file, which is restored
+/// if the exchange fails and ended by the next call after a crash.
+/// @ left: `$HOME/.neko/git/arukana@libnya`.
+/// @ right: `$HOME/.neko/rollback/arukana@libnya`.
+pub fn exchange(left: &Path, right: &Path) -> io::Result<()> {
+    let swapped: PathBuf = left.with_extension(SPEC_SWAP_EXT);
+
+    try!(recover(left, right));
+    match (exists(left), exists(right)) {
+        (true, true) => match rename_exchange(left, right) {
+            Some(result) => result,
+            None => {
+                try!(fs::rename(left, &swapped));
+                if let Err(why) = fs::rename(right, left) {
+                    let _ = fs::rename(&swapped, left);
+                    Err(why)
+                } else {
+                    fs::rename(&swapped, right)
+                }
+            },
+        },
+        (true, false) => fs::rename(left, right),
+        (false, true) => fs::rename(right, left),
+        (false, false) => Ok(()),
+    }
+}
+
+/// The function `recover` ends a exchange interrupted after the left
+/// one was moved to its swap file: the exchange is completed if the
+/// right one was already moved, else the left one is put back.
+/// @ left: `$HOME/.neko/git/arukana@libnya`.
+/// @ right: `$HOME/.neko/rollback/arukana@libnya`.
+pub fn recover(left: &Path, right: &Path) -> io::Result<()> {
+    let swapped: PathBuf = left.with_extension(SPEC_SWAP_EXT);
+
+    match (exists(&swapped), exists(left), exists(right)) {
+        (true, true, false) => fs::rename(&swapped, right),
+        (true, false, _) => fs::rename(&swapped, left),
+        (true, true, true) => Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                                                 "the swap file is left with both paths")),
+        (false, _, _) => Ok(()),
+    }
+}
+
+/// The function `rename_exchange` exchanges two paths by `renameat2`,
+/// or returns None if the kernel or the file system doesn't support it.
+#[cfg(target_os = "linux")]
+fn rename_exchange(left: &Path, right: &Path) -> Option<io::Result<()>> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut left: Vec<u8> = left.as_os_str().as_bytes().to_vec();
+    let mut right: Vec<u8> = right.as_os_str().as_bytes().to_vec();
+
+    left.push(b'\0');
+    right.push(b'\0');
+    unsafe {
+        if libc::syscall(libc::SYS_renameat2,
+                         libc::AT_FDCWD, left.as_ptr() as *const libc::c_char,
+                         libc::AT_FDCWD, right.as_ptr() as *const libc::c_char,
+                         SPEC_RENAME_EXCHANGE).eq(&0) {
+            Some(Ok(()))
+        } else {
+            let why: io::Error = io::Error::last_os_error();
+
+            match why.raw_os_error() {
+                Some(libc::ENOSYS) | Some(libc::EINVAL) | Some(libc::ENOTSUP) => None,
+                _ => Some(Err(why)),
+            }
+        }
+    }
+}
+
+/// The function `rename_exchange` isn't supported without `renameat2`.
+#[cfg(not(target_os = "linux"))]
+fn rename_exchange(_: &Path, _: &Path) -> Option<io::Result<()>> {
+    None
+}
@@ -0,0 +1,354 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::ops::Not;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
+
+use ::libc;
+use ::toml;
+
+use super::err::{CompositerError, Result};
+
+/// The name of trusted accounts label.
+const SPEC_TRUST_ACCOUNTS: &'static str = "accounts";
+/// The name of trusted hosts label.
+const SPEC_TRUST_HOSTS: &'static str = "hosts";
+/// The name of untrusted dependencies's policy label.
+const SPEC_TRUST_DEPENDENCIES: &'static str = "dependencies";
+/// The name of signed commits label.
+const SPEC_TRUST_SIGNED: &'static str = "signed";
+/// The name of trusted keys label.
+const SPEC_TRUST_FINGERPRINTS: &'static str = "fingerprints";
+/// The policy which refuses the untrusted dependencies.
+const SPEC_TRUST_REFUSE: &'static str = "refuse";
+/// The policy which trusts the dependencies on first use.
+const SPEC_TRUST_FIRST_USE: &'static str = "trust";
+/// The command which verifies a signature.
+const SPEC_TRUST_GPG: &'static str = "gpg";
+/// The status of gpg for a good signature with its fingerprints.
+const SPEC_TRUST_VALIDSIG: &'static str = "[GNUPG:] VALIDSIG ";
+/// The host of a repository cloned from a local path.
+const SPEC_TRUST_LOCALHOST: &'static str = "localhost";
+/// The extention of a signature given to gpg.
+const SPEC_TRUST_SIG_EXT: &'static str = "sig";
+/// The mode of a signature given to gpg.
+const SPEC_TRUST_SIG_MODE: u32 = 0o600;
+
+/// The generation of signatures given to gpg.
+static SPEC_TRUST_GENERATION: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// The struct `Trust` is the Neko.trust policy of the accounts
+/// and of the hosts whose repositories can be built.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trust {
+    /// The trusted accounts with their host like `github.com/arukana`.
+    accounts: Vec<String>,
+    /// The trusted hosts like `github.com`.
+    hosts: Vec<String>,
+    /// The untrusted dependencies are trusted on first use
+    /// instead of refused.
+    first_use: bool,
+    /// The built commits must be signed.
+    signed: bool,
+    /// The fingerprints of the keys which can sign the built commits.
+    fingerprints: Vec<String>,
+}
+
+impl Trust {
+    /// The constructor method `from_path` returns the Neko.trust
+    /// or a empty Trust if the file doesn't exist.
+    /// @ path: `$HOME/.neko/Neko.trust`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match File::open(path) {
+            Err(ref why) if why.kind().eq(&io::ErrorKind::NotFound) => {
+                Ok(Trust::default())
+            },
+            Err(why) => Err(CompositerError::ReadTrust(why)),
+            Ok(mut descripter) => {
+                let mut buffer = String::new();
+                match descripter.read_to_string(&mut buffer) {
+                    Err(why) => Err(CompositerError::ReadTrust(why)),
+                    Ok(_) => buffer.parse::<Trust>(),
+                }
+            }
+        }
+    }
+
+    /// The method `save` writes the Neko.trust.
+    /// @ path: `$HOME/.neko/Neko.trust`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        File::create(path).and_then(|mut descripter|
+            descripter.write_all(self.to_string().as_bytes())
+        ).or_else(|why: io::Error| Err(CompositerError::WriteTrust(why)))
+    }
+
+    pub fn get_accounts(&self) -> &[String] {
+        &self.accounts
+    }
+
+    pub fn get_hosts(&self) -> &[String] {
+        &self.hosts
+    }
+
+    pub fn get_fingerprints(&self) -> &[String] {
+        &self.fingerprints
+    }
+
+    pub fn is_refusing(&self) -> bool {
+        self.first_use.not()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+
+    /// The method `is_trusted` returns true if the account on its host
+    /// or the host of a repository is trusted.
+    /// @ account: `arukana`.
+    /// @ host: `Some("github.com")`.
+    pub fn is_trusted(&self, account: &str, host: Option<&str>) -> bool {
+        let account: String = get_account(account, host);
+
+        self.accounts.iter().any(|trusted| trusted.eq(&account)) ||
+        host.map_or(false, |host| self.hosts.iter().any(|trusted| trusted.eq(host)))
+    }
+
+    /// The method `is_fingerprint` returns true if the key
+    /// can sign the built commits.
+    /// @ fingerprint: `D8692123C4065DEA5E0F3AB5249B39D24F25E3B6`.
+    pub fn is_fingerprint(&self, fingerprint: &str) -> bool {
+        let fingerprint: String = get_fingerprint(fingerprint);
+
+        self.fingerprints.iter().any(|trusted| trusted.eq(&fingerprint))
+    }
+
+    /// The method `insert_account` trusts a account on its host.
+    /// @ account: `github.com/arukana`.
+    pub fn insert_account(&mut self, account: String) {
+        if self.accounts.contains(&account).not() {
+            self.accounts.push(account);
+        }
+    }
+
+    /// The method `insert_fingerprint` trusts the signatures of a key.
+    /// @ fingerprint: `D8692123C4065DEA5E0F3AB5249B39D24F25E3B6`.
+    pub fn insert_fingerprint(&mut self, fingerprint: &str) {
+        let fingerprint: String = get_fingerprint(fingerprint);
+
+        if self.fingerprints.contains(&fingerprint).not() {
+            self.fingerprints.push(fingerprint);
+        }
+    }
+
+    /// The method `insert_host` trusts a host.
+    /// @ host: `github.com`.
+    pub fn insert_host(&mut self, host: String) {
+        if self.hosts.contains(&host).not() {
+            self.hosts.push(host);
+        }
+    }
+}
+
+impl FromStr for Trust {
+    type Err = CompositerError;
+
+    /// The function `from_str` parses a policy like
+    /// `accounts = ["github.com/arukana"]`, `hosts = ["github.com"]`,
+    /// `dependencies = "refuse"`, `signed = true` and
+    /// `fingerprints = ["D8692123C4065DEA5E0F3AB5249B39D24F25E3B6"]`.
+    fn from_str(buffer: &str) -> Result<Self> {
+        let strings = |table: &toml::Table, key: &str| -> Option<Vec<String>> {
+            match table.get(key) {
+                None => Some(Vec::new()),
+                Some(value) => value.as_slice().and_then(|values|
+                    values.iter()
+                          .map(|value| value.as_str().map(|value| value.to_string()))
+                          .collect::<Option<Vec<String>>>()
+                ),
+            }
+        };
+
+        toml::Parser::new(buffer).parse().and_then(|table|
+            match (strings(&table, SPEC_TRUST_ACCOUNTS),
+                   strings(&table, SPEC_TRUST_HOSTS),
+                   table.get(SPEC_TRUST_DEPENDENCIES)
+                        .map_or(Some(SPEC_TRUST_REFUSE), |value| value.as_str()),
+                   table.get(SPEC_TRUST_SIGNED)
+                        .map_or(Some(false), |value| value.as_bool()),
+                   strings(&table, SPEC_TRUST_FINGERPRINTS)) {
+                (Some(accounts), Some(hosts), Some(dependencies), Some(signed), Some(fingerprints))
+                if (dependencies.eq(SPEC_TRUST_REFUSE) ||
+                    dependencies.eq(SPEC_TRUST_FIRST_USE)) &&
+                   accounts.iter().all(|account| is_account(account)) => Some(Trust {
+                    accounts: accounts.iter()
+                                      .map(|account| account.to_lowercase())
+                                      .collect::<Vec<String>>(),
+                    hosts: hosts,
+                    first_use: dependencies.eq(SPEC_TRUST_FIRST_USE),
+                    signed: signed,
+                    fingerprints: fingerprints.iter()
+                                              .map(|fingerprint| get_fingerprint(fingerprint))
+                                              .collect::<Vec<String>>(),
+                }),
+                _ => None,
+            }
+        ).ok_or(CompositerError::ParseTrust)
+    }
+}
+
+impl ToString for Trust {
+    fn to_string(&self) -> String {
+        let mut table: toml::Table = toml::Table::new();
+
+        table.insert(SPEC_TRUST_ACCOUNTS.to_string(), toml::Value::Array(
+            self.accounts.iter()
+                .map(|account| toml::Value::String(account.clone()))
+                .collect::<Vec<toml::Value>>()
+        ));
+        table.insert(SPEC_TRUST_HOSTS.to_string(), toml::Value::Array(
+            self.hosts.iter()
+                .map(|host| toml::Value::String(host.clone()))
+                .collect::<Vec<toml::Value>>()
+        ));
+        table.insert(SPEC_TRUST_DEPENDENCIES.to_string(), toml::Value::String(
+            if self.first_use { SPEC_TRUST_FIRST_USE } else { SPEC_TRUST_REFUSE }.to_string()
+        ));
+        table.insert(SPEC_TRUST_SIGNED.to_string(), toml::Value::Boolean(self.signed));
+        table.insert(SPEC_TRUST_FINGERPRINTS.to_string(), toml::Value::Array(
+            self.fingerprints.iter()
+                .map(|fingerprint| toml::Value::String(fingerprint.clone()))
+                .collect::<Vec<toml::Value>>()
+        ));
+        toml::Value::Table(table).to_string()
+    }
+}
+
+/// The function `get_host` returns the host of a remote url,
+/// a local repository hasn't host.
+/// @ url: `https://github.com/Arukana/libnya.git`
+///        or `git@github.com:Arukana/libnya.git`.
+pub fn get_host(url: &str) -> Option<String> {
+    let (scheme, rest): (Option<&str>, &str) = match url.find("://") {
+        Some(index) => (Some(&url[..index]), &url[index + 3..]),
+        None => (None, url),
+    };
+
+    if scheme.eq(&Some("file")) || (scheme.is_none() && rest.contains(':').not()) {
+        None
+    } else {
+        rest.split(|c: char| c.eq(&'/') || c.eq(&':'))
+            .next()
+            .and_then(|authority| authority.rsplit('@').next())
+            .and_then(|host| if host.is_empty() {
+                None
+            } else {
+                Some(host.to_lowercase())
+            })
+    }
+}
+
+/// The function `get_account` returns the trusted name of a account
+/// on its host, a local repository is on `localhost`.
+/// @ account: `arukana`.
+/// @ host: `Some("github.com")`.
+pub fn get_account(account: &str, host: Option<&str>) -> String {
+    format!("{}/{}", host.unwrap_or(SPEC_TRUST_LOCALHOST), account).to_lowercase()
+}
+
+/// The function `is_account` returns true if a trusted account
+/// is named with its host like `github.com/arukana`.
+pub fn is_account(account: &str) -> bool {
+    let parts: Vec<&str> = account.split('/').collect::<Vec<&str>>();
+
+    parts.len().eq(&2) && parts.iter().all(|part| part.is_empty().not())
+}
+
+/// The function `get_fingerprint` returns a fingerprint in upper case
+/// without spaces.
+/// @ fingerprint: `d869 2123 c406 ...`.
+fn get_fingerprint(fingerprint: &str) -> String {
+    fingerprint.chars()
+               .filter(|c| c.is_whitespace().not())
+               .collect::<String>()
+               .to_uppercase()
+}
+
+/// The function `get_valid_fingerprints` returns the fingerprints of the
+/// good signatures from the status of gpg, the signing key and its primary key.
+/// @ status: `[GNUPG:] VALIDSIG <fingerprint> ... <primary fingerprint>`.
+pub fn get_valid_fingerprints(status: &str) -> Vec<String> {
+    status.lines()
+          .filter(|line| line.starts_with(SPEC_TRUST_VALIDSIG))
+          .flat_map(|line| {
+              let fields: Vec<&str> = line[SPEC_TRUST_VALIDSIG.len()..]
+                  .split_whitespace()
+                  .collect::<Vec<&str>>();
+
+              fields.first()
+                    .into_iter()
+                    .chain(fields.get(9))
+                    .map(|fingerprint| get_fingerprint(fingerprint))
+                    .collect::<Vec<String>>()
+          })
+          .collect::<Vec<String>>()
+}
+
+/// The function `verify_signature` returns true if gpg verifies
+/// the signature of a signed commit by one of the trusted keys,
+/// the other keys of the keyring aren't trusted.
+/// The signature is written to a new file of the Neko root,
+/// unique to the process and to the call.
+/// @ root: `$HOME/.neko`.
+/// @ signature: the armored signature of the commit.
+/// @ data: the signed content of the commit.
+pub fn verify_signature(trust: &Trust,
+                        root: &Path,
+                        signature: &[u8],
+                        data: &[u8])
+                        -> Result<bool> {
+    let path: PathBuf = root.join(format!(
+        "neko-{}-{}",
+        unsafe { libc::getpid() },
+        SPEC_TRUST_GENERATION.fetch_add(1, Ordering::SeqCst)
+    )).with_extension(SPEC_TRUST_SIG_EXT);
+
+    if let Err(why) = OpenOptions::new().write(true)
+                                        .create_new(true)
+                                        .mode(SPEC_TRUST_SIG_MODE)
+                                        .open(&path)
+                                        .and_then(|mut descripter| descripter.write_all(signature)) {
+        if why.kind().ne(&io::ErrorKind::AlreadyExists) {
+            let _ = fs::remove_file(&path);
+        }
+        return Err(CompositerError::Signature(why));
+    }
+    let verified: io::Result<bool> = Command::new(SPEC_TRUST_GPG)
+        .arg("--status-fd")
+        .arg("1")
+        .arg("--verify")
+        .arg(&path)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(ref mut stdin) = child.stdin {
+                try!(stdin.write_all(data));
+            }
+            child.stdin.take();
+            child.wait_with_output()
+        })
+        .map(|output| {
+            output.status.success() &&
+            get_valid_fingerprints(&String::from_utf8_lossy(&output.stdout))
+                .iter()
+                .any(|fingerprint| trust.is_fingerprint(fingerprint))
+        });
+
+    let _ = fs::remove_file(&path);
+    verified.map_err(CompositerError::Signature)
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use ::semver::{Version, VersionReq};
+
+use super::err::{CompositerError, Result};
+
+/// The prefix of a tagged version like `v1.2.0`.
+const SPEC_VERSION_PREFIX: char = 'v';
+
+/// The function `parse_version` returns the version of a manifest
+/// like `0.3.1`.
+pub fn parse_version(version: &str) -> Result<Version> {
+    Version::parse(version).or_else(|_|
+        Err(CompositerError::ParseVersion(version.to_string())))
+}
+
+/// The function `parse_tag` returns the version of a tag like
+/// `v0.3.1` or `0.3.1`.
+pub fn parse_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.trim_left_matches(SPEC_VERSION_PREFIX)).ok()
+}
+
+/// The struct `Requirements` is a dictionary of library names and
+/// the version requirements declared by their dependents.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Requirements {
+    requirements: BTreeMap<String, Vec<(String, VersionReq)>>,
+}
+
+impl Requirements {
+    /// The method `insert` adds the requirement of a dependent.
+    /// @ libraryname: `arukana@libnya`.
+    /// @ dependent: `arukana@libmeow`.
+    /// @ requirement: `^0.3`.
+    pub fn insert(&mut self,
+                  libraryname: String,
+                  dependent: String,
+                  requirement: &str)
+                  -> Result<()> {
+        match VersionReq::parse(requirement) {
+            Err(_) => Err(CompositerError::ParseVersion(requirement.to_string())),
+            Ok(requirement) => {
+                self.requirements.entry(libraryname)
+                                 .or_insert_with(Vec::new)
+                                 .push((dependent, requirement));
+                Ok(())
+            },
+        }
+    }
+
+    /// The accessor method `get` returns the requirements
+    /// and their dependents for a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn get(&self, libraryname: &str) -> &[(String, VersionReq)] {
+        self.requirements.get(libraryname)
+                         .map(|requirements| &requirements[..])
+                         .unwrap_or_default()
+    }
+
+    /// The method `matches` returns true if a version
+    /// satisfies every requirement of a library.
+    /// @ libraryname: `arukana@libnya`.
+    pub fn matches(&self, libraryname: &str, version: &Version) -> bool {
+        self.get(libraryname)
+            .iter()
+            .all(|&(_, ref requirement)| requirement.matches(version))
+    }
+
+    /// The method `select` returns the highest tag which satisfies
+    /// every requirement of a library or else reports the conflict.
+    /// @ libraryname: `arukana@libnya`.
+    /// @ tags: `["v0.3.0", "v0.3.1", "v0.4.0"]`.
+    pub fn select<'a, I: IntoIterator<Item = &'a str>>(&self,
+                                                       libraryname: &str,
+                                                       tags: I)
+                                                       -> Result<String> {
+        tags.into_iter()
+            .filter_map(|tag| parse_tag(tag).and_then(|version| Some((version, tag))))
+            .filter(|&(ref version, _)| self.matches(libraryname, version))
+            .max_by_key(|&(ref version, _)| version.clone())
+            .and_then(|(_, tag)| Some(tag.to_string()))
+            .ok_or_else(|| CompositerError::VersionConflict(
+                libraryname.to_string(),
+                self.get(libraryname)
+                    .iter()
+                    .map(|&(ref dependent, ref requirement)|
+                        format!("{} requires {}", dependent, requirement))
+                    .collect::<Vec<String>>()
+            ))
+    }
+}
@@ -0,0 +1,119 @@
+use std::ffi::OsStr;
+use std::io;
+use std::mem;
+use std::ops::Not;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+
+use ::libc;
+
+/// The capacity of the inotify's buffer, by events's header.
+const SPEC_WATCH_CAPACITY: usize = 256;
+
+/// The struct `Watch` is a inotify's watcher of the files written
+/// or moved to a directory.
+#[derive(Debug)]
+pub struct Watch {
+    /// inotify's descriptor.
+    fd: libc::c_int,
+    /// Address of the watched directory.
+    path: PathBuf,
+}
+
+#[cfg(target_os = "linux")]
+impl Watch {
+    /// The constructor method `new` returns a non-blocking watcher
+    /// of a directory.
+    /// @ path: `$HOME/.neko/lib`.
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let mut pathname: Vec<u8> = path.as_os_str().as_bytes().to_vec();
+
+        pathname.push(b'\0');
+        unsafe {
+            let fd: libc::c_int = libc::inotify_init1(libc::IN_NONBLOCK |
+                                                      libc::IN_CLOEXEC);
+
+            if fd.eq(&-1) {
+                Err(io::Error::last_os_error())
+            } else if libc::inotify_add_watch(
+                fd,
+                pathname.as_ptr() as *const libc::c_char,
+                libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO
+            ).eq(&-1) {
+                let why: io::Error = io::Error::last_os_error();
+
+                libc::close(fd);
+                Err(why)
+            } else {
+                Ok(Watch {
+                    fd: fd,
+                    path: path,
+                })
+            }
+        }
+    }
+
+    /// The method `changes` returns without blocking the files
+    /// written or moved to the directory since the last call.
+    pub fn changes(&self) -> Vec<PathBuf> {
+        let mut buffer: [libc::inotify_event; SPEC_WATCH_CAPACITY] =
+            unsafe { mem::zeroed() };
+        let capacity: usize = mem::size_of_val(&buffer);
+        let header: usize = mem::size_of::<libc::inotify_event>();
+        let mut changes: Vec<PathBuf> = Vec::new();
+
+        loop {
+            let len: isize = unsafe {
+                libc::read(self.fd, buffer.as_mut_ptr() as *mut libc::c_void, capacity)
+            };
+            if len <= 0 {
+                return changes;
+            }
+            let bytes: &[u8] = unsafe {
+                ::std::slice::from_raw_parts(buffer.as_ptr() as *const u8, len as usize)
+            };
+            let mut offset: usize = 0;
+
+            while offset + header <= bytes.len() {
+                let event: &libc::inotify_event = unsafe {
+                    &*(bytes[offset..].as_ptr() as *const libc::inotify_event)
+                };
+                let name: &[u8] = &bytes[offset + header..
+                                         offset + header + event.len as usize];
+                let name: &[u8] = name.split(|&c| c.eq(&b'\0'))
+                                      .next()
+                                      .unwrap_or_default();
+
+                if name.is_empty().not() {
+                    let path: PathBuf = self.path.join(OsStr::from_bytes(name));
+
+                    if changes.contains(&path).not() {
+                        changes.push(path);
+                    }
+                }
+                offset += header + event.len as usize;
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Watch {
+    /// The constructor method `new` isn't supported without inotify.
+    pub fn new(_: PathBuf) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Other, "inotify is required"))
+    }
+
+    /// The method `changes` never returns a file without inotify.
+    pub fn changes(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
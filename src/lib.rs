@@ -38,7 +38,9 @@
 extern crate pty_proc;
 extern crate editeur;
 extern crate git2;
+extern crate semver;
 extern crate toml;
+extern crate tar;
 extern crate libc;
 
 #[macro_use]
@@ -50,6 +52,8 @@ pub mod display;
 pub mod prelude;
 /// The module `dynamic` is the compositer of extern libraries.
 pub mod dynamic;
+/// The module `plugin` is the interface of a library written in Rust.
+pub mod plugin;
 
 mod err;
 
@@ -59,6 +63,7 @@ use std::fmt;
 use std::char;
 
 use dynamic::Compositer;
+use dynamic::capability::Capabilities;
 use dynamic::library::state::LibraryState;
 use dynamic::library::state::Relative;
 use dynamic::library::state::persona::{Position, Cardinal};
@@ -184,6 +189,44 @@ impl <T> Neko<T> where T: Parent {
                         .split_whitespace()
                         .collect::<Vec<&str>>()
                         .as_slice()[..] {
+                &[ref answer..] if self.dynamic.get_legacy().is_empty().not() => {
+                    state.set_input_keyown('\u{3}');
+                    let accepted: bool = match answer {
+                        &["y"] | &["yes"] => true,
+                        _ => false,
+                    };
+
+                    match self.dynamic.migrate(accepted) {
+                        Ok(()) if accepted => format_subneko!(self,
+                            b"The libraries are granted what they request."
+                        ),
+                        Ok(()) => format_subneko!(self,
+                            b"The libraries aren't granted, approve them with `neko grant`."
+                        ),
+                        Err(why) => {
+                            format_subneko_err!(self, "libraries", "grant", why);
+                        },
+                    }
+                },
+                &[ref answer..] if self.dynamic.get_untrusted().is_some() => {
+                    state.set_input_keyown('\u{3}');
+                    match (answer, self.dynamic.get_untrusted().map(|account| account.to_string())) {
+                        (&["y"], Some(account)) | (&["yes"], Some(account)) => {
+                            match self.dynamic.confirm(true) {
+                                Ok(()) => format_subneko!(self,
+                                    format!("Trusted {}, installing...", account).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, account, "trust", why);
+                                },
+                            }
+                        },
+                        _ => {
+                            self.dynamic.confirm(false);
+                            format_subneko!(self, b"The installation is cancelled.");
+                        },
+                    }
+                },
                 &["neko", ref arguments..] => {
                     state.set_input_keyown('\u{3}');
                     match arguments {
@@ -195,11 +238,147 @@ impl <T> Neko<T> where T: Parent {
                                 ).as_bytes()
                             );
                         },
+                        &["list"] => {
+                            match self.dynamic.list() {
+                                Ok(list) => format_subneko!(self, list.as_bytes()),
+                                Err(why) => {
+                                    format_subneko_err!(self, "libraries", "list", why);
+                                },
+                            }
+                        },
+                        &["info", ref libraryname] => {
+                            match self.dynamic.info(libraryname) {
+                                Ok(info) => format_subneko!(self, info.as_bytes()),
+                                Err(why) => {
+                                    format_subneko_err!(self, libraryname, "inspect", why);
+                                },
+                            }
+                        },
+                        &["search", ref term] => {
+                            match self.dynamic.search(term) {
+                                Ok(entries) => format_subneko!(self, entries.as_bytes()),
+                                Err(why) => {
+                                    format_subneko_err!(self, term, "search", why);
+                                },
+                            }
+                        },
+                        &["bundle", "create", ref libraryname] => {
+                            match self.dynamic.bundle(libraryname, None::<&str>) {
+                                Ok(path) => format_subneko!(self,
+                                    format!("Bundled to {}.", path.display()).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, libraryname, "bundle", why);
+                                },
+                            }
+                        },
+                        &["bundle", "create", ref libraryname, ref path] => {
+                            match self.dynamic.bundle(libraryname, Some(path)) {
+                                Ok(path) => format_subneko!(self,
+                                    format!("Bundled to {}.", path.display()).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, libraryname, "bundle", why);
+                                },
+                            }
+                        },
+                        &["install", "--bundle", ref path] => {
+                            format_subneko!(self, path, "install",
+                                self.dynamic.install_bundle(path)
+                            );
+                        },
+                        &["install", "--locked"] => {
+                            format_subneko!(self, "Neko.lock", "install",
+                                self.dynamic.install_locked()
+                            );
+                        },
+                        &["install", ref repository, "--grant"] => {
+                            match self.dynamic.install_background(repository, Capabilities::all()) {
+                                Ok(()) => format_subneko!(self,
+                                    format!("Installing {}...", repository).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, repository, "install", why);
+                                },
+                            }
+                        },
+                        &["install", ref repository, "--grant", ref capabilities] => {
+                            match capabilities.parse::<Capabilities>() {
+                                Ok(capabilities) => {
+                                    match self.dynamic.install_background(repository, capabilities) {
+                                        Ok(()) => format_subneko!(self,
+                                            format!("Installing {}...", repository).as_bytes()
+                                        ),
+                                        Err(why) => {
+                                            format_subneko_err!(self, repository, "install", why);
+                                        },
+                                    }
+                                },
+                                Err(why) => {
+                                    format_subneko_err!(self, capabilities, "grant", why);
+                                },
+                            }
+                        },
                         &["install", ref repository] => {
-                            format_subneko!(self, repository, "install",
-                                self.dynamic.install(repository)
+                            match self.dynamic.install_background(repository, Capabilities::default()) {
+                                Ok(()) => format_subneko!(self,
+                                    format!("Installing {}...", repository).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, repository, "install", why);
+                                },
+                            }
+                        },
+                        &["grant", ref libraryname] => {
+                            format_subneko!(self, libraryname, "grant",
+                                self.dynamic.grant(libraryname, Capabilities::all())
+                            );
+                        },
+                        &["grant", ref libraryname, ref capabilities] => {
+                            match capabilities.parse::<Capabilities>() {
+                                Ok(capabilities) => {
+                                    format_subneko!(self, libraryname, "grant",
+                                        self.dynamic.grant(libraryname, capabilities)
+                                    );
+                                },
+                                Err(why) => {
+                                    format_subneko_err!(self, capabilities, "grant", why);
+                                },
+                            }
+                        },
+                        &["trust", "--host", ref host] => {
+                            format_subneko!(self, host, "trust",
+                                self.dynamic.trust_host(host)
+                            );
+                        },
+                        &["trust", "--key", ref fingerprint] => {
+                            format_subneko!(self, fingerprint, "trust",
+                                self.dynamic.trust_fingerprint(fingerprint)
+                            );
+                        },
+                        &["trust", ref account] => {
+                            format_subneko!(self, account, "trust",
+                                self.dynamic.trust(account)
+                            );
+                        },
+                        &["uninstall", ref libraryname, "--force"] => {
+                            format_subneko!(self, libraryname, "uninstall",
+                                self.dynamic.uninstall_forced(libraryname)
                             );
                         },
+                        &["autoremove"] => {
+                            match self.dynamic.autoremove() {
+                                Ok(ref removed) if removed.is_empty() => {
+                                    format_subneko!(self, b"No library to remove.");
+                                },
+                                Ok(removed) => format_subneko!(self,
+                                    format!("Removed {}.", removed.join(", ")).as_bytes()
+                                ),
+                                Err(why) => {
+                                    format_subneko_err!(self, "libraries", "autoremove", why);
+                                },
+                            }
+                        },
                         &["uninstall", ref libraryname] => {
                             format_subneko!(self, libraryname, "uninstall",
                                 self.dynamic.uninstall(libraryname)
@@ -231,6 +410,11 @@ impl <T> Neko<T> where T: Parent {
                                 self.dynamic.update(libraryname)
                             );
                         },
+                        &["rollback", ref libraryname] => {
+                            format_subneko!(self, libraryname, "rollback",
+                                self.dynamic.rollback(libraryname)
+                            );
+                        },
                         &["persona", ref x, ref y] => {
                             match (x.parse::<u16>(), y.parse::<u16>()) {
                                 (Ok(x), Ok(y)) => {
@@ -460,7 +644,13 @@ impl <T> Iterator for Neko<T> where T: Parent {
                 self.screen.set_window_size(size);
                 self.dynamic.resized(size);
             }
-            self.dynamic.call(&shell);
+            self.dynamic.call_with_screen(&shell,
+                                          self.shell.get_screen(),
+                                          self.shell.get_window_size());
+            let input: Vec<u8> = self.dynamic.take_input();
+            if input.is_empty().not() {
+                let _ = <T as io::Write>::write_all(&mut self.shell, &input);
+            }
             self.call();
             Some(shell)
         })
@@ -1,16 +1,3 @@
-#[macro_export]
-macro_rules! parse_number {
-    ($table: expr) => ({
-        use ::dynamic::SPEC_PRIORITY;
-        use ::dynamic::SPEC_PRIORITY_NAME;
-        if let Some(ref value) = $table.get(SPEC_PRIORITY_NAME) {
-            value.as_integer()
-        } else {
-            Some(SPEC_PRIORITY)
-        }
-    });
-}
-
 #[macro_export]
 macro_rules! format_subneko {
     ($slf: expr, $msg: expr) => ({
@@ -18,10 +5,9 @@ macro_rules! format_subneko {
         <T as io::Write>::write(&mut $slf.shell, $msg);
     });
     ($slf: expr, $arg: expr, $verb: expr, $command: expr) => ({
-        use std::error::Error;
         let output: String = match $command {
             Ok(_) => format!("{}ed with success.", $verb),
-            Err(why) => format!("Can't {} \"{}\" because: {}.", $verb, $arg, why.description()),
+            Err(why) => format!("Can't {} \"{}\" because: {}", $verb, $arg, why),
         };
         format_subneko!($slf, output.as_ref());
     });
@@ -30,9 +16,8 @@ macro_rules! format_subneko {
 #[macro_export]
 macro_rules! format_subneko_err {
     ($slf: expr, $arg: expr, $verb: expr, $why: expr) => ({
-        use std::error::Error;
         let output: String =
-            format!("Can't {} \"{}\" because: {}.", $verb, $arg, $why.description());
+            format!("Can't {} \"{}\" because: {}", $verb, $arg, $why);
         format_subneko!($slf, output.as_ref());
     });
 }
@@ -0,0 +1,38 @@
+/// The macro `export_plugin!` exports the `neko_abi_version`,
+/// the `neko_host` and the `on_event` for a type which implements
+/// `Plugin` and `Default`, the plugin is kept in the userdata
+/// of its library.
+#[macro_export]
+macro_rules! export_plugin {
+    ($plugin: ty) => {
+        /// The version of the `LibraryState`'s layout of the plugin.
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static neko_abi_version: $crate::plugin::c_uint = $crate::plugin::SPEC_ABI_VERSION;
+
+        #[no_mangle]
+        pub extern fn neko_host(table: *const $crate::plugin::Table) {
+            unsafe {
+                if let Some(table) = table.as_ref() {
+                    <$plugin as $crate::plugin::Plugin>::neko_host(table);
+                }
+            }
+        }
+
+        #[no_mangle]
+        pub extern fn on_event(state: *mut $crate::plugin::LibraryState,
+                               save: *mut *mut $crate::plugin::c_void,
+                               event: *const $crate::plugin::Event) {
+            unsafe {
+                if let Some(event) = event.as_ref() {
+                    $crate::plugin::hook(save as *mut *mut $plugin, state, |plugin: &mut $plugin, state|
+                        $crate::plugin::Plugin::on_event(plugin, state, event)
+                    );
+                    if event.get_kind().eq(&Some($crate::plugin::Kind::End)) {
+                        $crate::plugin::release(save as *mut *mut $plugin);
+                    }
+                }
+            }
+        }
+    };
+}
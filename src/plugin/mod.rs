@@ -0,0 +1,153 @@
+#[macro_use]
+mod macros;
+
+use std::ops::Not;
+use std::ptr;
+
+pub use ::libc::{c_void, c_char, c_uchar, c_int, c_uint, c_ushort, c_ulong, c_longlong, c_ulonglong};
+pub use ::pty::Winszed;
+pub use ::dynamic::library::{LibraryState, SPEC_ABI_VERSION};
+pub use ::dynamic::library::event::{Event, Kind};
+pub use ::dynamic::library::host::Table;
+
+/// The trait `Plugin` is a library written in Rust, each hook
+/// is a method which does nothing by default. The macro
+/// `export_plugin!` exports them to Neko through `on_event`.
+#[allow(unused_variables)]
+pub trait Plugin {
+    /// The function `neko_host` receives the host's table before
+    /// the first hook, the table is freed once the library is ended
+    /// and closed, so it mustn't be used after `end`.
+    fn neko_host(table: &'static Table) where Self: Sized {}
+
+    /// The method `install` is called when the library is installed.
+    fn install(&mut self, state: &mut LibraryState) {}
+
+    /// The method `uninstall` is called when the library is uninstalled.
+    fn uninstall(&mut self, state: &mut LibraryState) {}
+
+    /// The method `start` is called when the library is mounted.
+    fn start(&mut self, state: &mut LibraryState) {}
+
+    /// The method `end` is called when the library is unmounted,
+    /// the plugin is dropped after.
+    fn end(&mut self, state: &mut LibraryState) {}
+
+    /// The method `idle` is called when nothing happens.
+    fn idle(&mut self, state: &mut LibraryState) {}
+
+    /// The method `process` is called when the child current process
+    /// as been changed.
+    /// @ name: `b"vim"`.
+    fn process(&mut self, state: &mut LibraryState, name: &[u8], pid: c_int) {}
+
+    /// The method `command` is called when a command line is outputed
+    /// to the terminal.
+    /// @ line: `b"ls -la"`.
+    fn command(&mut self, state: &mut LibraryState, line: &[u8]) {}
+
+    /// The method `key_unicode_down` is called when a key is pressed.
+    fn key_unicode_down(&mut self, state: &mut LibraryState, code: c_ulonglong) {}
+
+    /// The method `key_string_down` is called when a text is pasted or pressed.
+    fn key_string_down(&mut self, state: &mut LibraryState, text: &[u8]) {}
+
+    /// The method `key_repeat_down` is called when a key is held.
+    fn key_repeat_down(&mut self, state: &mut LibraryState, repeat: c_ulong) {}
+
+    /// The method `key_interval_down` is called between two repetitions
+    /// of the held key.
+    fn key_interval_down(&mut self, state: &mut LibraryState, interval: c_longlong) {}
+
+    /// The method `mouse_pressed` is called when the mouse is pressed.
+    fn mouse_pressed(&mut self, state: &mut LibraryState, code: c_uint, xy: [c_ushort; 2]) {}
+
+    /// The method `mouse_released` is called when the mouse is released.
+    fn mouse_released(&mut self, state: &mut LibraryState, code: c_uint, xy: [c_ushort; 2]) {}
+
+    /// The method `input` is called when something is inputted
+    /// to the terminal, whatever it is.
+    fn input(&mut self, state: &mut LibraryState, text: &[u8]) {}
+
+    /// The method `output` is called when something is outputted
+    /// to the terminal, whatever it is.
+    fn output(&mut self, state: &mut LibraryState, text: &[u8]) {}
+
+    /// The method `resized` is called when the window is resized.
+    fn resized(&mut self, state: &mut LibraryState, size: &Winszed) {}
+
+    /// The method `on_event` is called instead of every hook, it
+    /// gives the event to the method of its kind by default and
+    /// ignores the unknown kinds.
+    fn on_event(&mut self, state: &mut LibraryState, event: &Event) {
+        match event.get_kind() {
+            Some(Kind::Install) => self.install(state),
+            Some(Kind::Uninstall) => self.uninstall(state),
+            Some(Kind::Start) => self.start(state),
+            Some(Kind::End) => self.end(state),
+            Some(Kind::Idle) => self.idle(state),
+            Some(Kind::Process) => if let Some((name, pid)) = event.get_process() {
+                self.process(state, name, pid)
+            },
+            Some(Kind::Command) => if let Some(line) = event.get_text() {
+                self.command(state, line)
+            },
+            Some(Kind::KeyUnicodeDown) => if let Some(code) = event.get_key_unicode_down() {
+                self.key_unicode_down(state, code)
+            },
+            Some(Kind::KeyStringDown) => if let Some(text) = event.get_text() {
+                self.key_string_down(state, text)
+            },
+            Some(Kind::KeyRepeatDown) => if let Some(repeat) = event.get_key_repeat_down() {
+                self.key_repeat_down(state, repeat)
+            },
+            Some(Kind::KeyIntervalDown) => if let Some(interval) = event.get_key_interval_down() {
+                self.key_interval_down(state, interval)
+            },
+            Some(Kind::MousePressed) => if let Some((code, xy)) = event.get_mouse() {
+                self.mouse_pressed(state, code, xy)
+            },
+            Some(Kind::MouseReleased) => if let Some((code, xy)) = event.get_mouse() {
+                self.mouse_released(state, code, xy)
+            },
+            Some(Kind::Input) => if let Some(text) = event.get_text() {
+                self.input(state, text)
+            },
+            Some(Kind::Output) => if let Some(text) = event.get_text() {
+                self.output(state, text)
+            },
+            Some(Kind::Resized) => if let Some(size) = event.get_resized() {
+                self.resized(state, size)
+            },
+            None => {},
+        }
+    }
+}
+
+/// The function `hook` calls a method of the plugin with the state,
+/// the plugin is created by `Default` on its first hook.
+/// @ plugin: the userdata of the library.
+#[doc(hidden)]
+pub unsafe fn hook<P, F>(plugin: *mut *mut P, state: *mut LibraryState, method: F)
+    where P: Plugin + Default,
+          F: FnOnce(&mut P, &mut LibraryState) {
+    if plugin.is_null() {
+        return;
+    }
+    if let Some(state) = state.as_mut() {
+        if (*plugin).is_null() {
+            *plugin = Box::into_raw(Box::new(P::default()));
+        }
+        method(&mut **plugin, state);
+    }
+}
+
+/// The function `release` drops the plugin after the hook `end`.
+/// @ plugin: the userdata of the library.
+#[doc(hidden)]
+pub unsafe fn release<P: Plugin>(plugin: *mut *mut P) {
+    if plugin.is_null().not() && (*plugin).is_null().not() {
+        drop(Box::from_raw(*plugin));
+        *plugin = ptr::null_mut();
+    }
+}
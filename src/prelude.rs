@@ -5,8 +5,10 @@ pub use super::Display;
 pub use super::{Neko, NekoError};
 pub use super::pty::{Shell, ShellError, ShellState, DeviceState, Master, Winszed, Character, Control, Operate, Key, Mouse, Code, Termios};
 pub use super::dynamic::{Compositer, CompositerError};
+pub use super::dynamic::capability::{Capability, Capabilities};
 pub use super::dynamic::library::{Library, LibraryError, LibraryState};
 pub use super::graphic::{Graphic, GraphicError, Sheet, Tuple, SPEC_MAX_DRAW, SPEC_MAX_XY};
 pub use super::dynamic::library::state::tooltip::Tooltip;
 pub use super::dynamic::library::state::persona::{Persona, Position, Cardinal};
 pub use super::dynamic::library::state::Relative;
+pub use super::plugin::Plugin;
@@ -0,0 +1,111 @@
+extern crate neko;
+extern crate toml;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use neko::prelude::*;
+use neko::dynamic::build::{Build, Backend};
+
+/// The shared library extention of the platform.
+#[cfg(target_os = "macos")]
+const SPEC_LIB_EXT: &'static str = "dylib";
+/// The shared library extention of the platform.
+#[cfg(not(target_os = "macos"))]
+const SPEC_LIB_EXT: &'static str = "so";
+
+/// The function `build` returns the `[build]` section of a manifest.
+fn build(manifest: &str) -> Result<Build, CompositerError> {
+    Build::from_manifest(&toml::Parser::new(manifest).parse().unwrap())
+}
+
+/// The function `touch` creates a empty file and its directories.
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::File::create(path).unwrap();
+}
+
+#[test]
+fn test_build_backend() {
+    let source: PathBuf = PathBuf::from("/tmp/arukana3/libnya");
+
+    assert_eq!(build("").unwrap(), Build::default());
+    assert_eq!(build("[build]\nbackend = \"make\"\n").unwrap().get_backend(),
+               &Backend::Make);
+    assert_eq!(build("[build]\nbackend = \"cmake\"\n").unwrap().get_backend(),
+               &Backend::Cmake);
+    assert_eq!(build("[build]\nbackend = \"cargo\"\n\
+                      command = \"./configure && make\"\n").unwrap().get_backend(),
+               &Backend::Command("./configure && make".to_string()));
+    assert_eq!(build("[build]\nartifact = \"out/libnya.so\"\n").unwrap().get_artifact(),
+               Some(&PathBuf::from("out/libnya.so")));
+    assert_eq!(build("[build]\nbackend = \"scons\"\n").err(),
+               Some(CompositerError::BuildBackend("scons".to_string())));
+
+    assert_eq!(Backend::Make.get_output(&source), source);
+    assert_eq!(Backend::Cargo.get_output(&source), source.join("target/release"));
+    assert_eq!(Backend::Cmake.get_output(&source), source.join("build"));
+    assert_eq!(Backend::Make.get_commands(&source).len(), 1);
+    assert_eq!(Backend::Cmake.get_commands(&source).len(), 2);
+}
+
+#[test]
+fn test_build_unvalid() {
+    assert_eq!(build("build = \"make\"\n").err(),
+               Some(CompositerError::ManifestKey("build".to_string(), String::new())));
+    assert_eq!(build("[build]\nbackend = 1\n").err(),
+               Some(CompositerError::ManifestKey("build.backend".to_string(), String::new())));
+    assert_eq!(build("[build]\ncommand = [\"make\"]\n").err(),
+               Some(CompositerError::ManifestKey("build.command".to_string(), String::new())));
+    assert_eq!(build("[build]\nartifact = true\n").err(),
+               Some(CompositerError::ManifestKey("build.artifact".to_string(), String::new())));
+    assert_eq!(build("[build]\nartifact = \"/tmp/libnya.so\"\n").err(),
+               Some(CompositerError::ManifestKey("build.artifact".to_string(), String::new())));
+    assert_eq!(build("[build]\nartifact = \"out/../../libnya.so\"\n").err(),
+               Some(CompositerError::ManifestKey("build.artifact".to_string(), String::new())));
+    assert_eq!(build("[build]\nartifact = \"./out/libnya.so\"\n").unwrap().get_artifact(),
+               Some(&PathBuf::from("./out/libnya.so")));
+}
+
+#[test]
+fn test_build_artifact() {
+    let source: PathBuf = env::temp_dir().join("neko-test-build");
+
+    let _ = fs::remove_dir_all(&source);
+    fs::create_dir_all(&source).unwrap();
+    assert_eq!(Build::default().find_artifact(&source, "libnya"), None);
+
+    touch(&source.join("nyan").with_extension(SPEC_LIB_EXT));
+    assert_eq!(Build::default().find_artifact(&source, "libnya"),
+               Some(source.join("nyan").with_extension(SPEC_LIB_EXT)));
+    touch(&source.join("meow").with_extension(SPEC_LIB_EXT));
+    assert_eq!(Build::default().find_artifact(&source, "libnya"), None);
+    touch(&source.join("liblibnya").with_extension(SPEC_LIB_EXT));
+    assert_eq!(Build::default().find_artifact(&source, "libnya"),
+               Some(source.join("liblibnya").with_extension(SPEC_LIB_EXT)));
+    touch(&source.join("libnya").with_extension(SPEC_LIB_EXT));
+    assert_eq!(Build::default().find_artifact(&source, "libnya"),
+               Some(source.join("libnya").with_extension(SPEC_LIB_EXT)));
+
+    assert_eq!(build("[build]\nbackend = \"cargo\"\n").unwrap().find_artifact(&source, "nya"),
+               None);
+    touch(&source.join("target/release/libnya").with_extension(SPEC_LIB_EXT));
+    assert_eq!(build("[build]\nbackend = \"cargo\"\n").unwrap().find_artifact(&source, "nya"),
+               Some(source.join("target/release/libnya").with_extension(SPEC_LIB_EXT)));
+
+    assert_eq!(build("[build]\nartifact = \"out/nya.so\"\n").unwrap()
+                   .find_artifact(&source, "libnya"),
+               None);
+    touch(&source.join("out/nya.so"));
+    assert_eq!(build("[build]\nartifact = \"out/nya.so\"\n").unwrap()
+                   .find_artifact(&source, "libnya"),
+               source.join("out/nya.so").canonicalize().ok());
+
+    touch(&env::temp_dir().join("neko-test-build-outside.so"));
+    ::std::os::unix::fs::symlink(env::temp_dir().join("neko-test-build-outside.so"),
+                                 source.join("out/escape.so")).unwrap();
+    assert_eq!(build("[build]\nartifact = \"out/escape.so\"\n").unwrap()
+                   .find_artifact(&source, "libnya"),
+               None);
+}
@@ -0,0 +1,146 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::ops::Not;
+use std::path::PathBuf;
+use std::process::Command;
+
+use neko::prelude::*;
+use neko::dynamic::bundle;
+use neko::dynamic::lock::{Lock, Locked};
+
+#[test]
+fn test_bundle_round_trip() {
+    let path: PathBuf = env::temp_dir().join("neko-test-bundle");
+    let git: PathBuf = path.join("git");
+    let mut lock: Lock = Lock::default();
+    let mut buffer: String = String::new();
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(git.join("arukana@libnya")).unwrap();
+    fs::File::create(git.join("arukana@libnya").join("Neko.toml")).unwrap()
+                                                                  .write_all(b"priority = 5\n")
+                                                                  .unwrap();
+    lock.insert("arukana@libnya".to_string(), Locked::new(
+        "https://github.com/Arukana/libnya.git".to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    ));
+    bundle::pack(&path.join("arukana@libnya.tar"), &git, &lock).unwrap();
+    assert_eq!(
+        bundle::unpack(&path.join("arukana@libnya.tar"), &path.join("bundle")).ok(),
+        Some(lock)
+    );
+    fs::File::open(path.join("bundle/git/arukana@libnya/Neko.toml")).unwrap()
+                                                                   .read_to_string(&mut buffer)
+                                                                   .unwrap();
+    assert_eq!(buffer, "priority = 5\n".to_string());
+    let _ = fs::remove_dir_all(&path);
+}
+
+#[test]
+fn test_bundle_cargo() {
+    let path: PathBuf = env::temp_dir().join("neko-test-bundle-cargo");
+    let git: PathBuf = path.join("git");
+    let mut lock: Lock = Lock::default();
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(git.join("arukana@libnya")).unwrap();
+    fs::File::create(git.join("arukana@libnya").join("Neko.toml")).unwrap()
+                                                                  .write_all(b"[build]\nbackend = \"cargo\"\n")
+                                                                  .unwrap();
+    lock.insert("arukana@libnya".to_string(), Locked::new(
+        "https://github.com/Arukana/libnya.git".to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    ));
+    bundle::pack(&path.join("arukana@libnya.tar"), &git, &lock).unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(path.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install_bundle(path.join("arukana@libnya.tar")).err(),
+               Some(CompositerError::BundleCargo("arukana@libnya".to_string())));
+    assert!(compositer.get_git().unwrap().join("arukana@libnya").exists().not());
+    let _ = fs::remove_dir_all(&path);
+}
+
+#[test]
+fn test_bundle_ungranted() {
+    let path: PathBuf = env::temp_dir().join("neko-test-bundle-ungranted");
+    let git: PathBuf = path.join("git");
+    let work: PathBuf = git.join("arukana@libnya");
+    let mut lock: Lock = Lock::default();
+
+    let _ = fs::remove_dir_all(&path);
+    common::fixture(&work);
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\ncapabilities = [\"input\"]\n").unwrap();
+    common::publish(&work, &path.join("arukana").join("libnya.git"));
+    common::git(&work, &["remote", "add", "origin",
+                         path.join("arukana").join("libnya.git").to_str().unwrap()]);
+    lock.insert("arukana@libnya".to_string(), Locked::new(
+        path.join("arukana").join("libnya.git").display().to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    ));
+    bundle::pack(&path.join("arukana@libnya.tar"), &git, &lock).unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(path.join(".neko")).unwrap();
+
+    assert_eq!(compositer.trust("localhost/arukana").err(), None);
+    assert_eq!(compositer.install_bundle(path.join("arukana@libnya.tar")).err(), None);
+    assert_eq!(compositer.get_mount().unwrap()
+                         .get("arukana@libnya")
+                         .and_then(|mounted| mounted.get_capabilities().cloned()),
+               Some(Capabilities::default()));
+    assert_eq!(compositer.into_iter()
+                         .map(|library| *library.get_capabilities())
+                         .collect::<Vec<Capabilities>>(),
+               vec![Capabilities::default()]);
+    assert_eq!(compositer.get_requested(&"arukana@libnya").ok(),
+               "input".parse::<Capabilities>().ok());
+    let _ = fs::remove_dir_all(&path);
+}
+
+#[test]
+fn test_bundle_name() {
+    let path: PathBuf = env::temp_dir().join("neko-test-bundle-name");
+    let archive: PathBuf = path.join("archive");
+    let mut lock: Lock = Lock::default();
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&archive).unwrap();
+    lock.insert("../escape".to_string(), Locked::new(
+        "https://github.com/Arukana/libnya.git".to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    ));
+    fs::File::create(archive.join("Neko.lock")).unwrap()
+                                               .write_all(lock.to_string().as_bytes())
+                                               .unwrap();
+    assert!(Command::new("tar").arg("-cf").arg(path.join("escape.tar"))
+                               .arg("-C").arg(&archive)
+                               .arg("Neko.lock")
+                               .status().unwrap()
+                               .success());
+
+    let mut compositer: Compositer = Compositer::with_root(path.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install_bundle(path.join("escape.tar")).err(),
+               Some(CompositerError::BundleName("../escape".to_string())));
+    assert!(compositer.get_root().join("escape").exists().not());
+    assert!(compositer.get_lock().unwrap().get("../escape").is_none());
+    let _ = fs::remove_dir_all(&path);
+}
@@ -0,0 +1,26 @@
+extern crate neko;
+
+use neko::prelude::*;
+
+#[test]
+fn test_capability_parse() {
+    let capabilities: Capabilities = "input, persona".parse::<Capabilities>().unwrap();
+
+    assert!(capabilities.contains(Capability::Input));
+    assert!(capabilities.contains(Capability::Persona));
+    assert!(!capabilities.contains(Capability::Lock));
+    assert_eq!(capabilities.to_string(), "input,persona".to_string());
+    assert_eq!("".parse::<Capabilities>().ok(), Some(Capabilities::default()));
+    assert_eq!("input,keylogger".parse::<Capabilities>().err(),
+               Some(CompositerError::ParseCapability("keylogger".to_string())));
+}
+
+#[test]
+fn test_capability_granted() {
+    let requested: Capabilities = "input,output,lock".parse::<Capabilities>().unwrap();
+    let granted: Capabilities = "output,persona".parse::<Capabilities>().unwrap();
+
+    assert_eq!(requested.intersection(&granted).to_string(), "output".to_string());
+    assert_eq!(requested.difference(&granted).to_string(), "input,lock".to_string());
+    assert_eq!(requested.intersection(&Capabilities::all()), requested);
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use neko::dynamic::library::SPEC_ABI_VERSION;
+
+/// The Makefile of the fixture, which builds `libnya.so` by cc.
+pub const SPEC_MAKEFILE: &'static str = "libnya.so: nya.c\n\
+                                         \tcc -shared -fPIC -o libnya.so nya.c\n";
+
+/// The function `git` runs a git command from a directory.
+pub fn git(path: &Path, args: &[&str]) {
+    assert!(Command::new("git").current_dir(path)
+                               .arg("-c").arg("user.name=neko")
+                               .arg("-c").arg("user.email=neko@localhost")
+                               .args(args)
+                               .status().unwrap()
+                               .success());
+}
+
+/// The function `abi` returns the C declaration of the
+/// `neko_abi_version` of the running layout.
+pub fn abi() -> String {
+    format!("const unsigned int neko_abi_version = {};\n", SPEC_ABI_VERSION)
+}
+
+/// The function `fixture` writes the manifest, the Makefile
+/// and the source of `libnya` to a directory.
+/// @ path: `/tmp/neko-test/libnya`.
+pub fn fixture(path: &Path) {
+    fs::create_dir_all(path).unwrap();
+    fs::File::create(path.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n").unwrap();
+    fs::File::create(path.join("Makefile")).unwrap()
+        .write_all(SPEC_MAKEFILE.as_bytes()).unwrap();
+    fs::File::create(path.join("nya.c")).unwrap()
+        .write_all(abi().as_bytes()).unwrap();
+}
+
+/// The function `publish` commits a directory and pushes it
+/// to a new bare repository.
+/// @ work: `/tmp/neko-test/libnya`.
+/// @ bare: `/tmp/neko-test/arukana/libnya.git`.
+pub fn publish(work: &Path, bare: &Path) {
+    fs::create_dir_all(bare).unwrap();
+    git(bare, &["init", "-q", "--bare"]);
+    git(work, &["init", "-q"]);
+    git(work, &["add", "-A"]);
+    git(work, &["commit", "-q", "-m", "nya"]);
+    git(work, &["push", "-q", bare.to_str().unwrap(), "HEAD"]);
+}
@@ -1,5 +1,8 @@
 extern crate neko;
 
+#[cfg(feature = "compositer_command")]
+mod common;
+
 #[cfg(feature = "compositer_command")]
 use neko::prelude::*;
 
@@ -7,6 +10,10 @@ use neko::prelude::*;
 use self::std::path::PathBuf;
 #[cfg(feature = "compositer_command")]
 use self::std::env;
+#[cfg(feature = "compositer_command")]
+use self::std::fs;
+#[cfg(feature = "compositer_command")]
+use self::std::io::Write;
 
 #[test]
 #[cfg(feature = "compositer_command")]
@@ -28,49 +35,82 @@ fn test_compositer_command() {
         );
     }
     {
+        let _ = fs::remove_dir_all("/tmp/arukana3/.neko");
         env::set_var("NEKO_PATH", "/tmp/arukana3/.neko");
         let mut compositer: Compositer = Compositer::new().unwrap();
+        let libnya: PathBuf = PathBuf::from("/tmp/arukana3/libnya");
+
+        let _ = fs::remove_dir_all(&libnya);
+        common::fixture(&libnya);
 
         assert_eq!(compositer.install(
-            "https://github.com/Arukana/libnya.git"
+            libnya.to_str().unwrap()
         ).err(), None);
         assert_eq!(compositer.mount(
-            &"arukana@libnya", None
+            &"local@libnya", None
         ).err(), None);
         assert_eq!(compositer.mount(
-            &"arukana@libnya", None
+            &"local@libnya", None
         ).err(), None);
         assert_eq!(compositer.update(
-            "arukana@libnya"
+            "local@libnya"
         ).err(), None);
         assert_eq!(compositer.install(
-            "https://github.com/Arukana/libnya.git"
+            libnya.to_str().unwrap()
         ).err(), Some(CompositerError::InstallExists));
         assert_eq!(compositer.unmount(
-            "arukana@libnya"
+            "local@libnya"
         ).err(), None);
         assert_eq!(compositer.unmount(
-            "arukana@libnya"
+            "local@libnya"
         ).err(), Some(CompositerError::UnmountPosition));
         assert_eq!(compositer.uninstall(
-            &"arukana@libnya"
+            &"local@libnya"
         ).err(), None);
         assert_eq!(compositer.unmount(
-            "arukana@libnya"
+            "local@libnya"
         ).err(), Some(CompositerError::UnmountPosition));
     }
     {
+        let _ = fs::remove_dir_all("/tmp/arukana4/.neko");
         env::set_var("NEKO_PATH", "/tmp/arukana4/.neko");
         let mut compositer: Compositer = Compositer::new().unwrap();
+        let libnya: PathBuf = PathBuf::from("/tmp/arukana4/libnya");
+
+        let _ = fs::remove_dir_all(&libnya);
+        common::fixture(&libnya);
 
         assert_eq!(compositer.install(
-            "https://github.com/Arukana/libnya.git"
+            libnya.to_str().unwrap()
         ).err(), None);
         assert_eq!(compositer.uninstall(
-            &"arukana@libnya"
+            &"local@libnya"
         ).err(), None);
         assert_eq!(compositer.uninstall(
-            &"arukana@libnya"
+            &"local@libnya"
         ).ok(), None);
+
+        fs::File::create(libnya.join("nya.c")).unwrap()
+            .write_all(b"int nya = 1;\n").unwrap();
+        match compositer.install(libnya.to_str().unwrap()) {
+            Err(CompositerError::Mount(LibraryError::NoAbi)) => {},
+            _ => panic!("A library without `neko_abi_version` is mounted."),
+        }
     }
 }
+
+#[test]
+#[cfg(feature = "compositer_command")]
+fn test_compositer_with_root() {
+    let current: PathBuf = env::current_dir().unwrap();
+    let nya: Compositer = Compositer::with_root("/tmp/arukana5/.neko").unwrap();
+    let meow: Compositer = Compositer::with_root("/tmp/arukana6/.neko").unwrap();
+
+    assert_eq!(nya.get_git().ok(),
+               Some(PathBuf::from("/tmp/arukana5/.neko/git"))
+    );
+    assert_eq!(meow.get_lib().ok(),
+               Some(PathBuf::from("/tmp/arukana6/.neko/lib"))
+    );
+    assert_eq!(env::current_dir().ok(), Some(current));
+}
@@ -0,0 +1,83 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::library::event::{self, Event};
+
+#[test]
+fn test_event_text() {
+    let text: Vec<u8> = b"nya".to_vec();
+    let event: Event = Event::text(event::Kind::Output, &text);
+
+    assert_eq!(event.get_version(), event::SPEC_EVENT_VERSION);
+    assert_eq!(event.get_kind(), Some(event::Kind::Output));
+    assert_eq!(event.get_text(), Some(&b"nya"[..]));
+    assert_eq!(event.get_process(), None);
+    assert_eq!(event.get_key_unicode_down(), None);
+}
+
+#[test]
+fn test_event_payload() {
+    assert_eq!(Event::process(b"vim", 42).get_process(), Some((&b"vim"[..], 42)));
+    assert_eq!(Event::key_unicode_down(97).get_key_unicode_down(), Some(97));
+    assert_eq!(Event::key_repeat_down(3).get_key_repeat_down(), Some(3));
+    assert_eq!(Event::key_interval_down(-1).get_key_interval_down(), Some(-1));
+    assert_eq!(Event::mouse(event::Kind::MouseReleased, 1, [4, 2]).get_mouse(), Some((1, [4, 2])));
+    assert_eq!(Event::mouse(event::Kind::MouseReleased, 1, [4, 2]).get_kind(),
+               Some(event::Kind::MouseReleased));
+    assert_eq!(Event::from(event::Kind::Start).get_kind(), Some(event::Kind::Start));
+    assert_eq!(Event::from(event::Kind::Start).get_text(), None);
+    assert_eq!(Event::text(event::Kind::Command, b"").get_text(), Some(&b""[..]));
+}
+
+#[test]
+fn test_event_kind_name() {
+    assert_eq!(event::Kind::Install.get_name(), "install");
+    assert_eq!(event::Kind::KeyStringDown.get_name(), "key_string_down");
+    assert_eq!(event::Kind::Resized.get_name(), "resized");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_event_install() {
+    use std::env;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::path::PathBuf;
+
+    let root: PathBuf = env::temp_dir().join("neko-test-event-install");
+    let work: PathBuf = root.join("libnya");
+    let mut log: String = String::new();
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&work).unwrap();
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n\
+                     [build]\n\
+                     command = \"cc -shared -fPIC -I$NEKO_INCLUDE -o libnya.so nya.c\"\n").unwrap();
+    fs::File::create(work.join("nya.c")).unwrap()
+        .write_all(b"#include <neko.h>\n\
+                     const unsigned int neko_abi_version = NEKO_ABI_VERSION;\n\
+                     static const NekoHost *neko;\n\
+                     void neko_host(const NekoHost *host) { neko = host; }\n\
+                     void install(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         neko->log(neko, (const unsigned char *)\"install\", 7);\n\
+                     }\n\
+                     void uninstall(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         neko->log(neko, (const unsigned char *)\"uninstall\", 9);\n\
+                     }\n").unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install(work.to_str().unwrap()).err(), None);
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: install\n");
+    assert_eq!(compositer.uninstall(&"local@libnya").err(), None);
+    log.clear();
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: install\nlocal@libnya: uninstall\n");
+    let _ = fs::remove_dir_all(&root);
+}
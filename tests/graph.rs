@@ -0,0 +1,58 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::graph::Graph;
+
+#[test]
+fn test_graph_order() {
+    let mut graph: Graph = Graph::default();
+
+    graph.insert("arukana@libnya".to_string(),
+                 vec!["arukana@libmeow".to_string(), "arukana@libpurr".to_string()]);
+    graph.insert("arukana@libmeow".to_string(), vec!["arukana@libpurr".to_string()]);
+    graph.insert("arukana@libpurr".to_string(), Vec::new());
+    assert_eq!(
+        graph.order("arukana@libnya").ok(),
+        Some(vec!["arukana@libpurr".to_string(),
+                  "arukana@libmeow".to_string(),
+                  "arukana@libnya".to_string()])
+    );
+    assert_eq!(
+        graph.order("arukana@libmeow").ok(),
+        Some(vec!["arukana@libpurr".to_string(),
+                  "arukana@libmeow".to_string()])
+    );
+}
+
+#[test]
+fn test_graph_cycle() {
+    let mut graph: Graph = Graph::default();
+
+    graph.insert("arukana@libnya".to_string(), vec!["arukana@libmeow".to_string()]);
+    graph.insert("arukana@libmeow".to_string(), vec!["arukana@libpurr".to_string()]);
+    graph.insert("arukana@libpurr".to_string(), vec!["arukana@libmeow".to_string()]);
+    assert_eq!(
+        graph.order("arukana@libnya").err(),
+        Some(CompositerError::DependencyCycle(vec!["arukana@libmeow".to_string(),
+                                                   "arukana@libpurr".to_string(),
+                                                   "arukana@libmeow".to_string()]))
+    );
+    assert_eq!(
+        graph.order_all().err(),
+        Some(CompositerError::DependencyCycle(vec!["arukana@libmeow".to_string(),
+                                                   "arukana@libpurr".to_string(),
+                                                   "arukana@libmeow".to_string()]))
+    );
+}
+
+#[test]
+fn test_graph_dependents() {
+    let mut graph: Graph = Graph::default();
+
+    graph.insert("arukana@libnya".to_string(), vec!["arukana@libmeow".to_string()]);
+    graph.insert("arukana@libpurr".to_string(), vec!["arukana@libmeow".to_string()]);
+    graph.insert("arukana@libmeow".to_string(), Vec::new());
+    assert_eq!(graph.get_dependents("arukana@libmeow"),
+               vec!["arukana@libnya".to_string(), "arukana@libpurr".to_string()]);
+    assert!(graph.get_dependents("arukana@libnya").is_empty());
+}
@@ -0,0 +1,75 @@
+extern crate neko;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use neko::prelude::*;
+use neko::dynamic::library::event;
+use neko::dynamic::library::host::SPEC_HOST_VERSION;
+use neko::dynamic::library::{header, SPEC_ABI_VERSION};
+
+#[test]
+fn test_header_generate() {
+    let header: String = header::generate();
+
+    assert!(header.contains(&format!("# define NEKO_ABI_VERSION {}", SPEC_ABI_VERSION)));
+    assert!(header.contains(&format!("# define NEKO_HOST_VERSION {}", SPEC_HOST_VERSION)));
+    assert!(header.contains(&format!("# define NEKO_EVENT_VERSION {}", event::SPEC_EVENT_VERSION)));
+    assert!(header.contains("#define NEKO_EVENT_KEY_STRING_DOWN 8\n"));
+    assert!(header.contains("_Static_assert(sizeof(NekoLibraryState) == 18708"));
+    assert!(header.contains("_Static_assert(sizeof(NekoCharacter) == 12"));
+    assert!(header.contains("    uint32_t glyph;\n"));
+    assert!(header.contains("extern const unsigned int neko_abi_version;\n"));
+    assert!(header.contains("void neko_host(const NekoHost *host);\n"));
+}
+
+#[test]
+fn test_header_character() {
+    let character: Character = Character::from('\u{10FFFF}');
+    let glyph: u32 = unsafe { *(&character as *const Character as *const u32) };
+
+    assert_eq!(glyph, '\u{10FFFF}' as u32);
+}
+
+#[test]
+fn test_header_compile() {
+    let path: PathBuf = env::temp_dir().join("neko-test-header");
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    fs::File::create(path.join(header::SPEC_HEADER)).unwrap()
+        .write_all(header::generate().as_bytes()).unwrap();
+    fs::File::create(path.join("nya.c")).unwrap()
+        .write_all(b"#include \"neko.h\"\n\
+                     const unsigned int neko_abi_version = NEKO_ABI_VERSION;\n\
+                     static const NekoHost *neko;\n\
+                     void neko_host(const NekoHost *host) {\n\
+                         neko = host;\n\
+                     }\n\
+                     void start(NekoLibraryState *state, void **save) {\n\
+                         (void)save;\n\
+                         state->lock = 1;\n\
+                         neko->set_tooltip(neko, state, (const unsigned char *)\"nya\", 3);\n\
+                     }\n\
+                     void on_event(NekoLibraryState *state, void **save, const NekoEvent *event) {\n\
+                         (void)save;\n\
+                         if (event->kind == NEKO_EVENT_OUTPUT && event->payload.text.len > 0) {\n\
+                             state->lock = event->payload.text.bytes[0] == 'n';\n\
+                         }\n\
+                     }\n").unwrap();
+    assert!(Command::new("cc").arg("-std=c11")
+                              .arg("-Wall").arg("-Werror")
+                              .arg("-shared").arg("-fPIC")
+                              .arg("-o").arg(path.join("libnya.so"))
+                              .arg(path.join("nya.c"))
+                              .status().unwrap()
+                              .success());
+
+    let hooks: Vec<&str> = Library::get_hooks(&path.join("libnya.so")).unwrap();
+
+    assert!(hooks.contains(&"start"));
+    assert!(hooks.contains(&"on_event"));
+}
@@ -0,0 +1,99 @@
+extern crate neko;
+
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use neko::prelude::*;
+use neko::dynamic::library::host::{Host, Binding, Table, SPEC_HOST_VERSION};
+
+#[test]
+fn test_host_state() {
+    let host: Rc<Host> = Rc::new(Host::default());
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        "persona".parse::<Capabilities>().unwrap(),
+                                        host);
+    let table: &Table = binding.as_table();
+    let mut state: LibraryState = LibraryState::default();
+    let mut tooltip: Tooltip = Tooltip::default();
+
+    assert_eq!(table.get_version(), SPEC_HOST_VERSION);
+    assert!(table.set_tooltip(&mut state, "nya"));
+    tooltip.set_message("nya".to_string());
+    assert_eq!(state.get_tooltip(), &tooltip);
+    assert!(table.set_tooltip_cardinal(&mut state, Relative::Top));
+    tooltip.set_cardinal(Relative::Top);
+    assert_eq!(state.get_tooltip(), &tooltip);
+    assert!(table.set_emotion(&mut state, &[]).not());
+    assert!(table.set_position_cardinal(&mut state, Cardinal::UpperLeft));
+}
+
+#[test]
+fn test_host_capabilities() {
+    let host: Rc<Host> = Rc::new(Host::default());
+    let refused: Binding = Binding::new("arukana@libnya".to_string(),
+                                        Capabilities::default(),
+                                        host.clone());
+    let granted: Binding = Binding::new("arukana@libnya".to_string(),
+                                        "input,output".parse::<Capabilities>().unwrap(),
+                                        host.clone());
+
+    assert!(refused.as_table().write(b"ls\n").not());
+    assert!(host.take_input().is_empty());
+    assert!(granted.as_table().write(b"ls\n"));
+    assert_eq!(host.take_input(), b"ls\n".to_vec());
+    assert!(host.take_input().is_empty());
+    assert!(refused.as_table().get_cell(0, 0).is_none());
+    assert!(granted.as_table().get_cell(0, 0).is_none());
+}
+
+#[test]
+fn test_host_persona() {
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        "input,output".parse::<Capabilities>().unwrap(),
+                                        Rc::new(Host::default()));
+    let table: &Table = binding.as_table();
+    let mut state: LibraryState = LibraryState::default();
+    let previous: LibraryState = state;
+
+    assert!(table.set_tooltip(&mut state, "nya").not());
+    assert!(table.set_tooltip_cardinal(&mut state, Relative::Top).not());
+    assert!(table.set_sheet(&mut state, "Bust").not());
+    assert!(table.set_position_cardinal(&mut state, Cardinal::UpperLeft).not());
+    assert!(table.set_position_cartesian(&mut state, [4, 2]).not());
+    assert_eq!(state.get_tooltip(), previous.get_tooltip());
+}
+
+#[test]
+fn test_host_unmount() {
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        Capabilities::default(),
+                                        Rc::new(Host::default()));
+
+    assert!(binding.is_unmounting().not());
+    assert!(binding.as_table().unmount());
+    assert!(binding.is_unmounting());
+    assert_eq!(binding.as_table().get_version(), SPEC_HOST_VERSION);
+}
+
+#[test]
+fn test_host_log() {
+    let path: PathBuf = env::temp_dir().join("neko-test-host.log");
+    let mut log: String = String::new();
+
+    let _ = fs::remove_file(&path);
+    assert!(Binding::new("arukana@libnya".to_string(),
+                         Capabilities::default(),
+                         Rc::new(Host::default())).as_table().log("meow").not());
+
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        Capabilities::default(),
+                                        Rc::new(Host::new(Some(path.clone()))));
+
+    assert!(binding.as_table().log("meow"));
+    fs::File::open(&path).unwrap().read_to_string(&mut log).unwrap();
+    assert_eq!(log, "arukana@libnya: meow\n");
+}
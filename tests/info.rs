@@ -0,0 +1,84 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use neko::prelude::*;
+
+/// The function `compile` builds a dynamic library from a C source.
+fn compile(source: &Path, library: &Path, code: &str) {
+    fs::File::create(source).unwrap()
+        .write_all(code.as_bytes()).unwrap();
+    assert!(Command::new("cc").arg("-shared").arg("-fPIC")
+                              .arg("-o").arg(library)
+                              .arg(source)
+                              .status().unwrap()
+                              .success());
+}
+
+#[test]
+fn test_info_hooks() {
+    let path: PathBuf = env::temp_dir().join("neko-test-info-hooks");
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    compile(&path.join("nya.c"), &path.join("libnya.so"),
+            &format!("{}void start(void) {{}}\nvoid output(void) {{}}\n", common::abi()));
+    assert_eq!(Library::get_hooks(&path.join("libnya.so")).unwrap(),
+               vec!["start", "output"]);
+
+    compile(&path.join("meow.c"), &path.join("libmeow.so"), "void start(void) {}\n");
+    match Library::get_hooks(&path.join("libmeow.so")) {
+        Err(LibraryError::NoAbi) => {},
+        _ => panic!("A library without ABI is inspected."),
+    }
+
+    fs::File::create(path.join("libpurr.so")).unwrap()
+        .write_all(b"purr").unwrap();
+    match Library::get_hooks(&path.join("libpurr.so")) {
+        Err(why @ LibraryError::BadDyLib(_)) => {
+            assert!(why.to_string().contains("libpurr.so"));
+        },
+        _ => panic!("A unvalid library is inspected."),
+    }
+}
+
+#[test]
+fn test_info_list() {
+    let root: PathBuf = env::temp_dir().join("neko-test-info-list");
+    let work: PathBuf = root.join("libnya");
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&work).unwrap();
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n\
+                     version = \"0.3.1\"\n\
+                     description = \"A cat which meows.\"\n\
+                     priority = 5\n").unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+    let source: PathBuf = compositer.get_git().unwrap().join("local@libnya");
+    let library: PathBuf = compositer.get_lib().unwrap().join("local@libnya.so");
+
+    unix::fs::symlink(&work, &source).unwrap();
+    compile(&work.join("nya.c"), &library,
+            &format!("{}void idle(void) {{}}\n", common::abi()));
+    assert_eq!(compositer.list().unwrap(),
+               format!("local@libnya unmounted 5 {} -", library.display()));
+    assert_eq!(compositer.mount(&"local@libnya", Some(-1)).err(), None);
+    assert_eq!(compositer.list().unwrap(),
+               format!("local@libnya mounted -1 {} -", library.display()));
+
+    let info: String = compositer.info(&"local@libnya").unwrap();
+
+    assert!(info.lines().any(|line| line.eq("hooks: idle")));
+    assert!(info.lines().any(|line| line.eq("dependencies: ")));
+    assert_eq!(compositer.info(&"local@libmeow").err(),
+               Some(CompositerError::OpenDirLib(io::Error::from(io::ErrorKind::NotFound))));
+}
@@ -0,0 +1,56 @@
+#![cfg(not(target_os = "macos"))]
+
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::process::Command;
+
+use neko::prelude::*;
+use neko::dynamic::build::Build;
+
+#[test]
+fn test_legacy_artifact() {
+    let source: PathBuf = env::temp_dir().join("neko-test-legacy-artifact");
+
+    let _ = fs::remove_dir_all(&source);
+    fs::create_dir_all(&source).unwrap();
+    fs::File::create(source.join("libnya.dylib")).unwrap();
+    assert_eq!(Build::default().find_artifact(&source, "libnya"),
+               Some(source.join("libnya.dylib")));
+    fs::File::create(source.join("libnya.so")).unwrap();
+    assert_eq!(Build::default().find_artifact(&source, "libnya"),
+               Some(source.join("libnya.so")));
+}
+
+#[test]
+fn test_legacy_build() {
+    let root: PathBuf = env::temp_dir().join("neko-test-legacy-build");
+    let work: PathBuf = root.join("libnya");
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&work);
+    common::publish(&work, &root.join("arukana/libnya.git"));
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+    let source: PathBuf = compositer.get_git().unwrap().join("arukana@libnya");
+    let legacy: PathBuf = compositer.get_lib().unwrap().join("arukana@libnya.dylib");
+    let library: PathBuf = legacy.with_extension("so");
+
+    common::git(&root, &["clone", "-q", "arukana/libnya.git", source.to_str().unwrap()]);
+    assert!(Command::new("cc").arg("-shared").arg("-fPIC")
+                              .arg("-o").arg(&legacy)
+                              .arg(work.join("nya.c"))
+                              .status().unwrap()
+                              .success());
+    assert_eq!(compositer.mount(&"arukana@libnya", None).err(), None);
+
+    assert_eq!(compositer.trust("localhost/arukana").err(), None);
+    assert_eq!(compositer.build(&source, "arukana@libnya").err(), None);
+    assert!(legacy.exists().not());
+    assert!(library.is_file());
+}
@@ -0,0 +1,69 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::lock::{Lock, Locked};
+
+#[test]
+fn test_lock_parse() {
+    let lock: Lock = "[\"arukana@libnya\"]\n\
+                      source = \"https://github.com/Arukana/libnya.git\"\n\
+                      revision = \"branch=dev\"\n\
+                      commit = \"0123456789abcdef0123456789abcdef01234567\"\n\
+                      dependencies = [\"arukana@libmeow\"]\n\
+                      checksum = \"89abcdef0123456789abcdef0123456789abcdef\"\n"
+        .parse::<Lock>().unwrap();
+
+    assert_eq!(
+        lock.get("arukana@libnya"),
+        Some(&Locked::new(
+            "https://github.com/Arukana/libnya.git".to_string(),
+            "branch=dev".to_string(),
+            "0123456789abcdef0123456789abcdef01234567".to_string(),
+            vec!["arukana@libmeow".to_string()],
+            "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+        ))
+    );
+    assert_eq!(lock.get("arukana@libmeow"), None);
+}
+
+#[test]
+fn test_lock_unvalid() {
+    assert_eq!(
+        "[\"arukana@libnya\"]\nsource = \"libnya.git\"\n".parse::<Lock>().err(),
+        Some(CompositerError::ParseLock)
+    );
+    assert_eq!(
+        "[\"arukana@libnya\"".parse::<Lock>().err(),
+        Some(CompositerError::ParseLock)
+    );
+}
+
+#[test]
+fn test_lock_round_trip() {
+    let mut lock: Lock = Lock::default();
+
+    lock.insert("arukana@libnya".to_string(), Locked::new(
+        "https://github.com/Arukana/libnya.git".to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    ));
+    assert_eq!(lock.to_string().parse::<Lock>().ok(), Some(lock.clone()));
+    lock.remove("arukana@libnya");
+    assert_eq!(lock, Lock::default());
+}
+
+#[test]
+fn test_lock_dependency() {
+    let lock: Lock = "[\"arukana@libmeow\"]\n\
+                      source = \"https://github.com/Arukana/libmeow.git\"\n\
+                      commit = \"0123456789abcdef0123456789abcdef01234567\"\n\
+                      checksum = \"89abcdef0123456789abcdef0123456789abcdef\"\n\
+                      dependency = true\n"
+        .parse::<Lock>().unwrap();
+
+    assert_eq!(lock.get("arukana@libmeow").map(|locked| locked.is_dependency()),
+               Some(true));
+    assert_eq!(lock.to_string().parse::<Lock>().ok(), Some(lock.clone()));
+}
@@ -0,0 +1,93 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::build::Backend;
+use neko::dynamic::manifest::{Manifest, Dependency};
+
+#[test]
+fn test_manifest_parse() {
+    let manifest: Manifest = "name = \"libnya\"\n\
+                              version = \"0.3.1\"\n\
+                              authors = [\"adjivas <adjivas@users.noreply.github.com>\"]\n\
+                              description = \"A cat which meows.\"\n\
+                              priority = 5\n\
+                              neko = \"0.1.0\"\n\
+                              [build]\n\
+                              backend = \"cargo\"\n\
+                              [dependencies.libmeow]\n\
+                              git = \"https://github.com/Arukana/libmeow.git\"\n\
+                              version = \"^0.2\"\n"
+        .parse::<Manifest>().unwrap();
+
+    assert_eq!(manifest.get_name(), Some("libnya"));
+    assert_eq!(manifest.get_version().map(|version| version.to_string()),
+               Some("0.3.1".to_string()));
+    assert_eq!(manifest.get_authors(),
+               &["adjivas <adjivas@users.noreply.github.com>".to_string()]);
+    assert_eq!(manifest.get_description(), Some("A cat which meows."));
+    assert_eq!(manifest.get_priority(), 5);
+    assert_eq!(manifest.get_build().get_backend(), &Backend::Cargo);
+    assert_eq!(manifest.get_dependencies(),
+               &[("libmeow".to_string(),
+                  Dependency::new("https://github.com/Arukana/libmeow.git".to_string(),
+                                  Some("^0.2".to_string())))]);
+    assert_eq!(manifest.get_neko().map(|neko| neko.to_string()),
+               Some("0.1.0".to_string()));
+}
+
+#[test]
+fn test_manifest_default() {
+    let manifest: Manifest = "".parse::<Manifest>().unwrap();
+
+    assert_eq!(manifest.get_name(), None);
+    assert_eq!(manifest.get_priority(), 0);
+    assert_eq!(manifest.get_build().get_backend(), &Backend::Make);
+    assert!(manifest.get_dependencies().is_empty());
+}
+
+#[test]
+fn test_manifest_unvalid() {
+    assert_eq!(
+        "priority = \"high\"\n".parse::<Manifest>().err(),
+        Some(CompositerError::ManifestKey("priority".to_string(), "\"high\"".to_string()))
+    );
+    assert_eq!(
+        "version = \"one\"\n".parse::<Manifest>().err(),
+        Some(CompositerError::ManifestKey("version".to_string(), "\"one\"".to_string()))
+    );
+    assert_eq!(
+        "authors = \"adjivas\"\n".parse::<Manifest>().err(),
+        Some(CompositerError::ManifestKey("authors".to_string(), String::new()))
+    );
+    assert_eq!(
+        "[dependencies.libmeow]\nversion = \"^0.2\"\n".parse::<Manifest>().err(),
+        Some(CompositerError::ManifestKey("dependencies.libmeow.git".to_string(),
+                                          String::new()))
+    );
+    assert_eq!(
+        "[dependencies.libmeow]\ngit = \"libmeow.git\"\nversion = \"latest\"\n"
+            .parse::<Manifest>().err(),
+        Some(CompositerError::ManifestKey("dependencies.libmeow.version".to_string(),
+                                          "latest".to_string()))
+    );
+    assert_eq!(
+        "priority = ".parse::<Manifest>().err(),
+        Some(CompositerError::ParseManifest(String::new()))
+    );
+    assert!("name = \"libnya\"\npriority = ".parse::<Manifest>().err()
+                                            .map(|why| why.to_string().contains("2:"))
+                                            .unwrap_or_default());
+}
+
+#[test]
+fn test_manifest_capabilities() {
+    let manifest: Manifest = "capabilities = [\"input\", \"lock\"]\n"
+        .parse::<Manifest>().unwrap();
+
+    assert_eq!(manifest.get_capabilities().to_string(), "input,lock".to_string());
+    assert!("".parse::<Manifest>().unwrap().get_capabilities().is_empty());
+    assert_eq!(
+        "capabilities = [\"keylogger\"]\n".parse::<Manifest>().err(),
+        Some(CompositerError::ParseCapability("keylogger".to_string()))
+    );
+}
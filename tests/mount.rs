@@ -0,0 +1,177 @@
+extern crate neko;
+
+use std::ops::Not;
+use std::rc::Rc;
+
+use neko::prelude::*;
+use neko::dynamic::library::host::{Host, Binding};
+use neko::dynamic::mount::{Mount, Mounted};
+
+#[test]
+fn test_mount_parse() {
+    let mount: Mount = "[\"arukana@libnya\"]\n\
+                        enabled = false\n\
+                        priority = 5\n\
+                        [\"arukana@libmeow\"]\n\
+                        enabled = true\n"
+        .parse::<Mount>().unwrap();
+
+    assert_eq!(mount.get("arukana@libnya"), Some(&Mounted::new(false, Some(5))));
+    assert_eq!(mount.get("arukana@libmeow"), Some(&Mounted::new(true, None)));
+    assert_eq!(mount.get("arukana@libpurr"), None);
+}
+
+#[test]
+fn test_mount_unvalid() {
+    assert_eq!(
+        "[\"arukana@libnya\"]\npriority = 5\n".parse::<Mount>().err(),
+        Some(CompositerError::ParseMount)
+    );
+}
+
+#[test]
+fn test_mount_round_trip() {
+    let mut mount: Mount = Mount::default();
+
+    mount.insert("arukana@libnya".to_string(), Mounted::new(true, Some(-2)));
+    mount.insert("arukana@libmeow".to_string(), Mounted::new(false, None));
+    assert_eq!(mount.to_string().parse::<Mount>().ok(), Some(mount.clone()));
+    mount.remove("arukana@libmeow");
+    assert_eq!(mount.get("arukana@libmeow"), None);
+}
+
+#[test]
+fn test_mount_capabilities() {
+    let mount: Mount = "[\"arukana@libnya\"]\n\
+                        enabled = true\n\
+                        capabilities = [\"output\", \"persona\"]\n"
+        .parse::<Mount>().unwrap();
+    let mut mounted: Mounted = Mounted::new(true, None);
+
+    mounted.set_capabilities("output,persona".parse::<Capabilities>().unwrap());
+    assert_eq!(mount.get("arukana@libnya"), Some(&mounted));
+    assert_eq!(mount.to_string().parse::<Mount>().ok(), Some(mount.clone()));
+    assert_eq!(
+        "[\"arukana@libnya\"]\nenabled = true\ncapabilities = [\"keylogger\"]\n"
+            .parse::<Mount>().err(),
+        Some(CompositerError::ParseMount)
+    );
+}
+
+#[test]
+fn test_mount_given() {
+    let mount: Mount = "[\"arukana@libnya\"]\n\
+                        enabled = true\n\
+                        [\"arukana@libmeow\"]\n\
+                        enabled = true\n\
+                        capabilities = []\n\
+                        [\"arukana@libpurr\"]\n\
+                        enabled = true\n\
+                        capabilities = [\"output\", \"persona\"]\n"
+        .parse::<Mount>().unwrap();
+    let requested: Capabilities = "input,persona".parse::<Capabilities>().unwrap();
+
+    assert_eq!(mount.get("arukana@libnya").unwrap().get_capabilities(), None);
+    assert!(mount.get("arukana@libnya").unwrap().get_given(&requested).is_empty());
+    assert!(Mounted::default().get_given(&requested).is_empty());
+    assert!(mount.get("arukana@libmeow").unwrap().get_given(&requested).is_empty());
+    assert_eq!(mount.get("arukana@libpurr").unwrap().get_given(&requested),
+               "persona".parse::<Capabilities>().unwrap());
+    assert_eq!(mount.to_string().parse::<Mount>().ok(), Some(mount.clone()));
+}
+
+#[test]
+fn test_mount_ungranted_host() {
+    let host: Rc<Host> = Rc::new(Host::default());
+    let requested: Capabilities = "input,output".parse::<Capabilities>().unwrap();
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        Mounted::new(true, None).get_given(&requested),
+                                        host.clone());
+
+    assert!(binding.as_table().write(b"ls\n").not());
+    assert!(host.take_input().is_empty());
+    assert!(binding.as_table().get_cell(0, 0).is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_mount_ungranted() {
+    use std::env;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    let root: PathBuf = env::temp_dir().join("neko-test-mount-ungranted");
+    let source: PathBuf = root.join(".neko/git/local@libnya");
+    let library: PathBuf = root.join(".neko/lib/local@libnya.so");
+    let mut log: String = String::new();
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&source).unwrap();
+    fs::create_dir_all(root.join(".neko/lib")).unwrap();
+    fs::File::create(source.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n\
+                     capabilities = [\"input\", \"output\"]\n").unwrap();
+    fs::File::create(source.join("nya.c")).unwrap()
+        .write_all(b"#include <neko.h>\n\
+                     const unsigned int neko_abi_version = NEKO_ABI_VERSION;\n\
+                     static const NekoHost *neko;\n\
+                     void neko_host(const NekoHost *host) { neko = host; }\n\
+                     void start(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         if (neko->write(neko, (const unsigned char *)\"ls\", 2) == 0)\n\
+                             neko->log(neko, (const unsigned char *)\"write\", 5);\n\
+                         else\n\
+                             neko->log(neko, (const unsigned char *)\"refused\", 7);\n\
+                     }\n").unwrap();
+
+    // The Neko.mount of a library mounted before the capabilities.
+    fs::File::create(root.join(".neko/Neko.mount")).unwrap()
+        .write_all(b"[\"local@libnya\"]\nenabled = true\n").unwrap();
+    {
+        let compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+        assert!(Command::new("cc").arg("-shared").arg("-fPIC")
+                                  .arg("-I").arg(compositer.header().unwrap())
+                                  .arg("-o").arg(&library)
+                                  .arg(source.join("nya.c"))
+                                  .status().unwrap()
+                                  .success());
+    }
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.get_legacy(), &["local@libnya".to_string()]);
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: refused\n");
+    assert_eq!(compositer.migrate(true).err(), None);
+    assert!(compositer.get_legacy().is_empty());
+    log.clear();
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: refused\nlocal@libnya: write\n");
+    drop(compositer);
+
+    let compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert!(compositer.get_legacy().is_empty());
+    drop(compositer);
+    fs::remove_file(root.join(".neko/Neko.mount")).unwrap();
+
+    // A library dropped to the lib sub-directory isn't granted.
+    let compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert!(compositer.get_legacy().is_empty());
+    assert_eq!(Mount::from_path(&root.join(".neko/Neko.mount")).unwrap()
+                                                               .get("local@libnya")
+                                                               .and_then(|mounted| mounted.get_capabilities()),
+               Some(&Capabilities::default()));
+    drop(compositer);
+    log.clear();
+    fs::File::open(root.join(".neko/log/neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert!(log.ends_with("local@libnya: refused\n"));
+    let _ = fs::remove_dir_all(&root);
+}
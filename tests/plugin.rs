@@ -0,0 +1,110 @@
+#[macro_use]
+extern crate neko;
+
+use std::ops::Not;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use neko::prelude::*;
+use neko::dynamic::library::event::{self, Event};
+use neko::dynamic::library::host::{Host, Binding, Table, SPEC_HOST_VERSION};
+use neko::dynamic::library::SPEC_ABI_VERSION;
+
+/// The version of the host's table received by `neko_host`.
+static HOST: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct Nya {
+    started: bool,
+}
+
+impl Plugin for Nya {
+    fn neko_host(table: &'static Table) {
+        HOST.store(table.get_version() as usize, Ordering::SeqCst);
+    }
+
+    fn start(&mut self, state: &mut LibraryState) {
+        self.started = true;
+        state.set_lock(true);
+    }
+
+    fn key_string_down(&mut self, state: &mut LibraryState, text: &[u8]) {
+        if text.eq(b"nya") {
+            state.set_lock(false);
+        }
+    }
+
+    fn end(&mut self, state: &mut LibraryState) {
+        state.set_lock(self.started);
+    }
+}
+
+export_plugin!(Nya);
+
+#[test]
+fn test_plugin_export() {
+    let mut state: LibraryState = LibraryState::default();
+    let mut save: *mut neko::plugin::c_void = ptr::null_mut();
+
+    assert_eq!(neko_abi_version, SPEC_ABI_VERSION);
+    on_event(ptr::null_mut(), &mut save, &Event::from(event::Kind::Idle));
+    assert!(save.is_null());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::Start));
+    assert!(state.is_locked());
+    on_event(&mut state, &mut save, &Event::text(event::Kind::KeyStringDown, b"meow"));
+    assert!(state.is_locked());
+    on_event(&mut state, &mut save, &Event::text(event::Kind::KeyStringDown, b"nya"));
+    assert!(state.is_locked().not());
+    assert!(save.is_null().not());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::End));
+    assert!(state.is_locked());
+    assert!(save.is_null());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::End));
+    assert!(state.is_locked().not());
+}
+
+#[test]
+fn test_plugin_userdata() {
+    let mut state: LibraryState = LibraryState::default();
+    let mut save: *mut neko::plugin::c_void = ptr::null_mut();
+    let mut other: *mut neko::plugin::c_void = ptr::null_mut();
+
+    on_event(&mut state, &mut save, &Event::from(event::Kind::Start));
+    on_event(&mut state, &mut other, &Event::from(event::Kind::End));
+    assert!(state.is_locked().not());
+    assert!(save.is_null().not());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::End));
+    assert!(state.is_locked());
+    assert!(save.is_null());
+}
+
+#[test]
+fn test_plugin_host() {
+    let host: Rc<Host> = Rc::new(Host::default());
+    let binding: Binding = Binding::new("arukana@libnya".to_string(),
+                                        Capabilities::default(),
+                                        host);
+
+    neko_host(ptr::null());
+    assert_eq!(HOST.load(Ordering::SeqCst), 0);
+    neko_host(binding.as_table());
+    assert_eq!(HOST.load(Ordering::SeqCst), SPEC_HOST_VERSION as usize);
+}
+
+#[test]
+fn test_plugin_event() {
+    let mut state: LibraryState = LibraryState::default();
+    let mut save: *mut neko::plugin::c_void = ptr::null_mut();
+
+    on_event(&mut state, &mut save, ptr::null());
+    assert!(save.is_null());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::Start));
+    assert!(state.is_locked());
+    on_event(&mut state, &mut save, &Event::text(event::Kind::KeyStringDown, b"nya"));
+    assert!(state.is_locked().not());
+    assert!(save.is_null().not());
+    on_event(&mut state, &mut save, &Event::from(event::Kind::End));
+    assert!(state.is_locked());
+    assert!(save.is_null());
+}
@@ -0,0 +1,146 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+use neko::prelude::*;
+use neko::dynamic::lock::{Lock, Locked};
+use neko::dynamic::trust::Trust;
+use neko::dynamic::progress::{self, Progress, Reporter};
+
+#[test]
+fn test_progress_run() {
+    let mut log: Vec<u8> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    assert!(progress::run(Command::new("sh").arg("-c").arg("echo nya; echo meow >&2"),
+                          &mut log,
+                          |line| lines.push(line.to_string())).unwrap().success());
+    lines.sort();
+    assert_eq!(lines, vec!["meow".to_string(), "nya".to_string()]);
+
+    let mut log: Vec<&str> = ::std::str::from_utf8(&log).unwrap().lines().collect();
+
+    log.sort();
+    assert_eq!(log, vec!["meow", "nya"]);
+}
+
+#[test]
+fn test_progress_run_exit() {
+    let mut log: Vec<u8> = Vec::new();
+
+    assert!(progress::run(Command::new("sh").arg("-c").arg("exit 1"),
+                          &mut log,
+                          |_| {}).unwrap().success().not());
+    assert!(log.is_empty());
+}
+
+#[test]
+fn test_progress_install_background() {
+    let root: PathBuf = env::temp_dir().join("neko-test-background");
+    let work: PathBuf = root.join("libnya");
+    let meow: PathBuf = PathBuf::from("target").join("neko-test-background");
+    let bare: PathBuf = meow.join("meow").join("libmeow.git");
+
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&meow);
+    common::fixture(&meow.join("libmeow"));
+    common::publish(&meow.join("libmeow"), &bare);
+    common::fixture(&work);
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(format!("name = \"libnya\"\n\
+                            [dependencies.libmeow]\n\
+                            git = \"{}\"\n", bare.display()).as_bytes()).unwrap();
+    fs::create_dir_all(root.join(".neko")).unwrap();
+    fs::File::create(root.join(".neko").join("Neko.trust")).unwrap()
+        .write_all(b"dependencies = \"trust\"\n").unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install_background(work.to_str().unwrap(),
+                                             Capabilities::default()).err(),
+               None);
+    while compositer.poll_install() {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(compositer.get_trust().unwrap().is_trusted("meow", None));
+    assert_eq!(compositer.get_lock().unwrap()
+                         .get("meow@libmeow")
+                         .map(|locked| locked.is_dependency()),
+               Some(true));
+    assert!(compositer.get_lock().unwrap().get("local@libnya").is_some());
+    assert!(compositer.list().unwrap().lines().any(|line|
+        line.starts_with("meow@libmeow mounted")));
+    assert!(compositer.list().unwrap().lines().any(|line|
+        line.starts_with("local@libnya mounted")));
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&meow);
+}
+
+#[test]
+fn test_progress_reporter() {
+    let (sender, receiver) = ::std::sync::mpsc::channel();
+    let reporter: Reporter = Reporter::new(sender);
+    let mut lock: Lock = Lock::default();
+    let mut trust: Trust = Trust::default();
+    let locked: Locked = Locked::new(
+        "https://github.com/Arukana/libnya.git".to_string(),
+        String::new(),
+        "0123456789abcdef0123456789abcdef01234567".to_string(),
+        Vec::new(),
+        "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+    );
+
+    lock.insert("arukana@libmeow".to_string(), locked.clone());
+    reporter.send(Progress::Lock("arukana@libnya".to_string(), Some(locked)));
+    reporter.send(Progress::Lock("arukana@libmeow".to_string(), None));
+    reporter.send(Progress::Host("GitHub.com".to_string()));
+    reporter.send(Progress::Fingerprint("D8692123C4065DEA5E0F3AB5249B39D24F25E3B6".to_string()));
+    reporter.apply_lock(&mut lock);
+    reporter.apply_trust(&mut trust);
+    assert_eq!(reporter.get_locked(), vec!["arukana@libnya".to_string()]);
+    assert!(lock.get("arukana@libnya").is_some());
+    assert!(lock.get("arukana@libmeow").is_none());
+    assert_eq!(trust.get_hosts(), &["github.com".to_string()]);
+    assert!(trust.is_fingerprint("D8692123C4065DEA5E0F3AB5249B39D24F25E3B6"));
+    assert_eq!(receiver.try_iter().count(), 4);
+}
+
+#[test]
+fn test_progress_install_untrusted() {
+    let root: PathBuf = PathBuf::from("target").join("neko-test-background-untrusted");
+    let work: PathBuf = root.join("libnya");
+    let bare: PathBuf = root.join("arukana").join("libnya.git");
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&work);
+    common::publish(&work, &bare);
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install_background(bare.to_str().unwrap(),
+                                             Capabilities::default()).err(),
+               None);
+    while compositer.poll_install() {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(compositer.get_untrusted(), Some("localhost/arukana"));
+    assert!(compositer.get_git().unwrap().join("arukana@libnya").exists().not());
+    assert_eq!(compositer.confirm(true).err(), None);
+    assert_eq!(compositer.get_untrusted(), None);
+    while compositer.poll_install() {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(compositer.get_trust().unwrap().is_trusted("arukana", None));
+    assert!(compositer.list().unwrap().lines().any(|line|
+        line.starts_with("arukana@libnya mounted")));
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,97 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use neko::prelude::*;
+use neko::dynamic::registry::{Registry, Entry};
+
+#[test]
+fn test_registry_entry() {
+    assert_eq!(
+        "name = \"libnya\"\n\
+         description = \"A cat which meows.\"\n\
+         url = \"https://github.com/Arukana/libnya.git\"\n\
+         versions = [\"v0.3.0\", \"v0.3.1\"]\n".parse::<Entry>().ok(),
+        Some(Entry::new(
+            "libnya".to_string(),
+            "A cat which meows.".to_string(),
+            "https://github.com/Arukana/libnya.git".to_string(),
+            vec!["v0.3.0".to_string(), "v0.3.1".to_string()],
+        ))
+    );
+    assert_eq!(
+        "name = \"libnya\"\n".parse::<Entry>().err(),
+        Some(CompositerError::ParseRegistry)
+    );
+}
+
+#[test]
+fn test_registry_search() {
+    let mut registry: Registry = Registry::default();
+
+    registry.insert(Entry::new(
+        "libnya".to_string(),
+        "A cat which meows.".to_string(),
+        "https://github.com/Arukana/libnya.git".to_string(),
+        Vec::new(),
+    ));
+    registry.insert(Entry::new(
+        "libpurr".to_string(),
+        "A cat which purrs.".to_string(),
+        "https://github.com/Arukana/libpurr.git".to_string(),
+        Vec::new(),
+    ));
+    assert_eq!(
+        registry.get("libnya").map(|entry| entry.get_url()),
+        Some("https://github.com/Arukana/libnya.git")
+    );
+    assert_eq!(
+        registry.search("NYA").iter()
+                .map(|entry| entry.get_name())
+                .collect::<Vec<&str>>(),
+        vec!["libnya"]
+    );
+    assert_eq!(registry.search("cat").len(), 2);
+    assert!(registry.search("dog").is_empty());
+}
+
+#[test]
+fn test_registry_resolve() {
+    let root: PathBuf = env::temp_dir().join("neko-test-registry");
+    let registry: PathBuf = root.join("registry");
+    let libnya: PathBuf = root.join("arukana/libnya.git");
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&root.join("libnya"));
+    common::publish(&root.join("libnya"), &libnya);
+    fs::create_dir_all(&registry).unwrap();
+    fs::File::create(registry.join("libnya.toml")).unwrap()
+        .write_all(format!("name = \"libnya\"\n\
+                            description = \"A cat which meows.\"\n\
+                            url = \"{}\"\n\
+                            versions = [\"v0.3.1\"]\n", libnya.display()).as_bytes()).unwrap();
+    common::publish(&registry, &root.join("registry.git"));
+    env::set_var("NEKO_REGISTRY", root.join("registry.git"));
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.trust("localhost/arukana").err(), None);
+    assert_eq!(compositer.search("nya").ok(),
+               Some("libnya [v0.3.1] A cat which meows.".to_string()));
+    assert_eq!(compositer.install("libmeow").err(),
+               Some(CompositerError::RegistryName("libmeow".to_string())));
+    assert!(compositer.install("libmeow").err()
+                      .map(|why| why.to_string().contains("libmeow"))
+                      .unwrap_or_default());
+    assert_eq!(compositer.install("libnya").err(), None);
+    assert!(compositer.list().unwrap().starts_with("arukana@libnya mounted"));
+    assert_eq!(compositer.get_lock().unwrap()
+                         .get("arukana@libnya")
+                         .map(|locked| locked.get_source().to_string()),
+               Some(libnya.display().to_string()));
+}
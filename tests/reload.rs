@@ -0,0 +1,117 @@
+extern crate neko;
+
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::ops::Not;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use neko::prelude::*;
+
+#[test]
+fn test_reload_fallback() {
+    let root: PathBuf = env::temp_dir().join("neko-test-reload");
+
+    let _ = fs::remove_dir_all(&root);
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+    let source: PathBuf = compositer.get_git().unwrap().join("local@libnya");
+    let library: PathBuf = compositer.get_lib().unwrap().join("local@libnya.so");
+    let mut log: String = String::new();
+
+    fs::create_dir_all(&source).unwrap();
+    fs::File::create(source.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n").unwrap();
+    fs::File::create(source.join("nya.c")).unwrap()
+        .write_all(b"#include <neko.h>\n\
+                     const unsigned int neko_abi_version = NEKO_ABI_VERSION;\n\
+                     static const NekoHost *neko;\n\
+                     void neko_host(const NekoHost *host) { neko = host; }\n\
+                     void start(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         neko->log(neko, (const unsigned char *)\"start\", 5);\n\
+                     }\n\
+                     void end(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         neko->log(neko, (const unsigned char *)\"end\", 3);\n\
+                     }\n").unwrap();
+    assert!(Command::new("cc").arg("-shared").arg("-fPIC")
+                              .arg("-I").arg(compositer.header().unwrap())
+                              .arg("-o").arg(&library)
+                              .arg(source.join("nya.c"))
+                              .status().unwrap()
+                              .success());
+    assert_eq!(compositer.mount(&"local@libnya", None).err(), None);
+
+    // The rebuilt library is written aside then renamed, like by a build.
+    fs::File::create(library.with_extension("tmp")).unwrap()
+        .write_all(b"nya").unwrap();
+    fs::rename(library.with_extension("tmp"), &library).unwrap();
+    match compositer.reload(&"local@libnya") {
+        Err(CompositerError::Reload(LibraryError::BadDyLib(why))) => assert!(why.is_empty().not()),
+        _ => panic!("A unvalid library is reloaded."),
+    }
+    assert!(compositer.list().unwrap().contains("local@libnya mounted"));
+    assert_eq!(fs::metadata(root.join(".neko/copy")).unwrap().permissions().mode() & 0o777,
+               0o700);
+    assert!(fs::read_dir(root.join(".neko/copy")).unwrap().next().is_none());
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: start\n");
+
+    assert!(Command::new("cc").arg("-shared").arg("-fPIC")
+                              .arg("-I").arg(compositer.header().unwrap())
+                              .arg("-o").arg(library.with_extension("tmp"))
+                              .arg(source.join("nya.c"))
+                              .status().unwrap()
+                              .success());
+    fs::rename(library.with_extension("tmp"), &library).unwrap();
+    assert_eq!(compositer.reload(&"local@libnya").err(), None);
+    assert!(compositer.list().unwrap().contains("local@libnya mounted"));
+    log.clear();
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: start\nlocal@libnya: end\nlocal@libnya: start\n");
+
+    drop(compositer);
+    log.clear();
+    fs::File::open(root.join(".neko/log/neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: start\nlocal@libnya: end\n\
+                     local@libnya: start\nlocal@libnya: end\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_reload_installed() {
+    let root: PathBuf = env::temp_dir().join("neko-test-reload-installed");
+    let work: PathBuf = root.join("libnya");
+    let mut log: String = String::new();
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&work).unwrap();
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libnya\"\n\
+                     [build]\n\
+                     command = \"cc -shared -fPIC -I$NEKO_INCLUDE -o libnya.so nya.c\"\n").unwrap();
+    fs::File::create(work.join("nya.c")).unwrap()
+        .write_all(b"#include <neko.h>\n\
+                     const unsigned int neko_abi_version = NEKO_ABI_VERSION;\n\
+                     static const NekoHost *neko;\n\
+                     void neko_host(const NekoHost *host) { neko = host; }\n\
+                     void start(NekoLibraryState *state, void **save) {\n\
+                         (void)state; (void)save;\n\
+                         neko->log(neko, (const unsigned char *)\"start\", 5);\n\
+                     }\n").unwrap();
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+
+    assert_eq!(compositer.install(work.to_str().unwrap()).err(), None);
+    compositer.hot_reload();
+    fs::File::open(compositer.get_log().unwrap().join("neko.log")).unwrap()
+        .read_to_string(&mut log).unwrap();
+    assert_eq!(log, "local@libnya: start\n");
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,49 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::revision::Revision;
+
+#[test]
+fn test_revision_from_source() {
+    assert_eq!(
+        Revision::from_source("https://github.com/Arukana/libnya.git").ok(),
+        Some(("https://github.com/Arukana/libnya.git", Revision::Head))
+    );
+    assert_eq!(
+        Revision::from_source("https://github.com/Arukana/libnya.git#v1.2.0").ok(),
+        Some(("https://github.com/Arukana/libnya.git",
+              Revision::Tag("v1.2.0".to_string())))
+    );
+    assert_eq!(
+        Revision::from_source("git@github.com:Arukana/libnya.git#branch=dev").ok(),
+        Some(("git@github.com:Arukana/libnya.git",
+              Revision::Branch("dev".to_string())))
+    );
+    assert_eq!(
+        Revision::from_source("https://github.com/Arukana/libnya.git#rev=abc123").ok(),
+        Some(("https://github.com/Arukana/libnya.git",
+              Revision::Rev("abc123".to_string())))
+    );
+}
+
+#[test]
+fn test_revision_unvalid() {
+    assert_eq!(
+        "branch=".parse::<Revision>().err(),
+        Some(CompositerError::InstallFormat)
+    );
+    assert_eq!(
+        "commit=abc123".parse::<Revision>().err(),
+        Some(CompositerError::InstallFormat)
+    );
+}
+
+#[test]
+fn test_revision_display() {
+    assert_eq!(Revision::Head.to_string(), "");
+    assert_eq!(Revision::Tag("v1.2.0".to_string()).to_string(), "tag=v1.2.0");
+    assert_eq!(
+        Revision::Branch("dev".to_string()).to_string().parse::<Revision>().ok(),
+        Some(Revision::Branch("dev".to_string()))
+    );
+}
@@ -0,0 +1,141 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use neko::prelude::*;
+use neko::dynamic::swap;
+
+/// The function `head` returns the commit checked out by a repository.
+fn head(path: &Path) -> String {
+    let output = Command::new("git").current_dir(path)
+                                    .arg("rev-parse").arg("HEAD")
+                                    .output().unwrap();
+
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// The function `commit` writes a version of the library, commits it
+/// and pushes it to the bare repository.
+fn commit(work: &Path, manifest: &str, source: &str) -> String {
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(manifest.as_bytes()).unwrap();
+    fs::File::create(work.join("nya.c")).unwrap()
+        .write_all(source.as_bytes()).unwrap();
+    common::git(work, &["add", "-A"]);
+    common::git(work, &["commit", "-q", "-m", "nya"]);
+    common::git(work, &["push", "-q", "origin", "HEAD"]);
+    head(work)
+}
+
+#[test]
+fn test_rollback_update() {
+    let root: PathBuf = env::temp_dir().join("neko-test-rollback");
+    let bare: PathBuf = root.join("arukana").join("libnya.git");
+    let work: PathBuf = root.join("libnya");
+    let abi: String = common::abi();
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&work);
+    common::publish(&work, &bare);
+    common::git(&work, &["remote", "add", "origin", bare.to_str().unwrap()]);
+
+    let first: String = head(&work);
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+    let dest: PathBuf = compositer.get_git().unwrap().join("arukana@libnya");
+
+    common::git(&root, &["clone", "-q", bare.to_str().unwrap(), dest.to_str().unwrap()]);
+    assert_eq!(compositer.trust("localhost/arukana").err(), None);
+    assert_eq!(compositer.build(&dest, "arukana@libnya").err(), None);
+
+    let second: String = commit(&work, "name = \"libnya\"\n",
+                                &format!("{}int nya = 2;\n", abi));
+
+    assert_eq!(compositer.update("arukana@libnya").err(), None);
+    assert_eq!(head(&dest), second);
+    assert_eq!(compositer.rollback("arukana@libnya").err(), None);
+    assert_eq!(head(&dest), first);
+    assert_eq!(compositer.rollback("arukana@libnya").err(), None);
+    assert_eq!(head(&dest), second);
+
+    commit(&work, "name = \"libnya\"\n", "int nya = 3;\n");
+    assert!(compositer.update("arukana@libnya").is_err());
+    assert_eq!(head(&dest), second);
+
+    let meow: PathBuf = root.join("libmeow");
+
+    fs::create_dir_all(&meow).unwrap();
+    fs::File::create(meow.join("Neko.toml")).unwrap()
+        .write_all(b"name = \"libmeow\"\n").unwrap();
+    fs::File::create(meow.join("Makefile")).unwrap()
+        .write_all(b"all:\n\tfalse\n").unwrap();
+    commit(&work,
+           &format!("name = \"libnya\"\n\
+                     [dependencies.libmeow]\n\
+                     git = \"{}\"\n", meow.display()),
+           &format!("{}int nya = 4;\n", abi));
+    assert!(compositer.update("arukana@libnya").is_err());
+    assert_eq!(head(&dest), second);
+    assert!(fs::symlink_metadata(compositer.get_git().unwrap().join("local@libmeow")).is_err());
+    assert!(compositer.get_lib().unwrap().join("arukana@libnya.so").exists());
+    assert!(fs::read_dir(compositer.get_git().unwrap()).unwrap()
+                .chain(fs::read_dir(compositer.get_lib().unwrap()).unwrap())
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().extension().map_or(false, |extension|
+                    extension.eq(swap::SPEC_SWAP_EXT))).not());
+
+    // The staged version can't be remounted, so it isn't kept to be rolled back.
+    commit(&work, "name = \"libnya\"\n", &format!("{}int nya = 5;\n", abi));
+    fs::File::create(root.join(".neko").join("Neko.mount")).unwrap()
+        .write_all(b"nya").unwrap();
+    assert_eq!(compositer.update("arukana@libnya").err(), Some(CompositerError::ParseMount));
+    assert_eq!(head(&dest), second);
+    assert_eq!(compositer.rollback("arukana@libnya").err(), Some(CompositerError::Rollback));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_rollback_update_reuse() {
+    let root: PathBuf = env::temp_dir().join("neko-test-rollback-reuse");
+    let work: PathBuf = root.join("libnya");
+    let meow: PathBuf = root.join("libmeow");
+    let bare: PathBuf = root.join("arukana").join("libnya.git");
+    let abi: String = common::abi();
+    let manifest: String = format!("name = \"libnya\"\n\
+                                    [dependencies.libmeow]\n\
+                                    git = \"{}\"\n",
+                                   root.join("meow").join("libmeow.git").display());
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&meow);
+    common::publish(&meow, &root.join("meow").join("libmeow.git"));
+    common::fixture(&work);
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(manifest.as_bytes()).unwrap();
+    common::publish(&work, &bare);
+    common::git(&work, &["remote", "add", "origin", bare.to_str().unwrap()]);
+
+    let mut compositer: Compositer = Compositer::with_root(root.join(".neko")).unwrap();
+    let dest: PathBuf = compositer.get_git().unwrap().join("arukana@libnya");
+
+    common::git(&root, &["clone", "-q", bare.to_str().unwrap(), dest.to_str().unwrap()]);
+    assert_eq!(compositer.trust("localhost/arukana").err(), None);
+    assert_eq!(compositer.trust("localhost/meow").err(), None);
+    assert_eq!(compositer.build(&dest, "arukana@libnya").err(), None);
+
+    let libmeow: String = head(&compositer.get_git().unwrap().join("meow@libmeow"));
+    let second: String = commit(&work, &manifest, &format!("{}int nya = 2;\n", abi));
+
+    assert_eq!(compositer.update("arukana@libnya").err(), None);
+    assert_eq!(head(&dest), second);
+    assert_eq!(head(&compositer.get_git().unwrap().join("meow@libmeow")), libmeow);
+    assert!(compositer.get_rollback().unwrap().join("arukana@libnya").exists());
+    assert!(compositer.get_rollback().unwrap().join("meow@libmeow").exists().not());
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,88 @@
+extern crate neko;
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::ops::Not;
+use std::os::unix;
+use std::path::PathBuf;
+
+use neko::prelude::*;
+
+#[test]
+fn test_root_isolation() {
+    let root: PathBuf = env::temp_dir().join("neko-test-root");
+    let work: PathBuf = root.join("libnya");
+    let current: PathBuf = env::current_dir().unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+    common::fixture(&work);
+
+    let mut nya: Compositer = Compositer::with_root(root.join("nya/.neko")).unwrap();
+    let meow: Compositer = Compositer::with_root(root.join("meow/.neko")).unwrap();
+
+    assert_eq!(nya.get_root(), root.join("nya/.neko"));
+    assert_eq!(nya.get_git().ok(), Some(root.join("nya/.neko/git")));
+    assert_eq!(nya.get_lib().ok(), Some(root.join("nya/.neko/lib")));
+    assert_eq!(meow.get_git().ok(), Some(root.join("meow/.neko/git")));
+    assert_eq!(meow.get_lib().ok(), Some(root.join("meow/.neko/lib")));
+    assert_eq!(meow.get_rollback().ok(), Some(root.join("meow/.neko/rollback")));
+
+    let source: PathBuf = nya.get_git().unwrap().join("local@libnya");
+
+    unix::fs::symlink(&work, &source).unwrap();
+    assert_eq!(nya.build(&source, "local@libnya").err(), None);
+    assert!(nya.get_lib().unwrap().join("local@libnya.so").is_file());
+    assert!(nya.get_lock().unwrap().get("local@libnya").is_some());
+    assert!(root.join("nya/.neko/Neko.lock").is_file());
+    assert!(meow.get_lib().unwrap().join("local@libnya.so").exists().not());
+    assert!(meow.get_lock().unwrap().get("local@libnya").is_none());
+    assert!(root.join("meow/.neko/Neko.lock").exists().not());
+    assert!(meow.list().unwrap().is_empty());
+    assert_eq!(env::current_dir().ok(), Some(current));
+}
+
+#[test]
+fn test_root_failures() {
+    let root: PathBuf = env::temp_dir().join("neko-test-root-failures");
+    let work: PathBuf = root.join("libnya");
+    let meow: PathBuf = PathBuf::from("target").join("neko-test-root-failures");
+    let bare: PathBuf = meow.join("meow").join("libmeow.git");
+    let current: PathBuf = env::current_dir().unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&meow);
+    common::fixture(&meow.join("libmeow"));
+    common::publish(&meow.join("libmeow"), &bare);
+    common::fixture(&work);
+    fs::File::create(work.join("Neko.toml")).unwrap()
+        .write_all(format!("name = \"libnya\"\n\
+                            [dependencies.libmeow]\n\
+                            git = \"{}\"\n", bare.display()).as_bytes()).unwrap();
+    fs::File::create(root.join("libnya.tar")).unwrap()
+        .write_all(b"nya").unwrap();
+
+    let mut nya: Compositer = Compositer::with_root(root.join("nya/.neko")).unwrap();
+    let other: Compositer = Compositer::with_root(root.join("meow/.neko")).unwrap();
+
+    match nya.install(work.to_str().unwrap()) {
+        Err(CompositerError::Untrusted(account, _, _)) => {
+            assert_eq!(account, "localhost/meow".to_string());
+        },
+        _ => panic!("A untrusted dependency is built."),
+    }
+    assert!(nya.get_lock().unwrap().get("local@libnya").is_none());
+    assert!(nya.get_lib().unwrap().join("local@libnya.so").exists().not());
+    assert!(nya.install_bundle(root.join("libnya.tar")).is_err());
+    match nya.update("arukana@libnya") {
+        Err(CompositerError::UpdateRepOpen(_)) => {},
+        _ => panic!("A library which isn't installed is updated."),
+    }
+    assert!(other.list().unwrap().is_empty());
+    assert!(root.join("meow/.neko/Neko.lock").exists().not());
+    assert_eq!(env::current_dir().ok(), Some(current));
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&meow);
+}
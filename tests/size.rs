@@ -1,9 +1,11 @@
 extern crate neko;
 
 use neko::prelude::*;
+use neko::dynamic::library::SPEC_ABI_VERSION;
 
 use std::mem;
 
+/// The `SPEC_ABI_VERSION` must be incremented with any change of these sizes.
 #[test]
 fn test_size_library_state() {
     assert_eq!(mem::size_of::<LibraryState>(), 18708);
@@ -17,3 +19,8 @@ fn test_size_library_state() {
     assert_eq!(mem::size_of::<Character>(), 12);
     assert_eq!(mem::size_of::<[Character; 1024]>(), 12288);
 }
+
+#[test]
+fn test_size_abi_version() {
+    assert_eq!(SPEC_ABI_VERSION, 1);
+}
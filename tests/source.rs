@@ -0,0 +1,67 @@
+extern crate neko;
+
+use std::path::{Path, PathBuf};
+
+use neko::prelude::*;
+use neko::dynamic::revision::Revision;
+use neko::dynamic::source::Source;
+
+#[test]
+fn test_source_from_str() {
+    assert_eq!(
+        "https://github.com/Arukana/libnya.git#branch=dev".parse::<Source>().ok(),
+        Some(Source::Git("https://github.com/Arukana/libnya.git".to_string(),
+                         Revision::Branch("dev".to_string())))
+    );
+    assert_eq!(
+        "file:///home/neko/libnya".parse::<Source>().ok(),
+        Some(Source::Local(PathBuf::from("/home/neko/libnya")))
+    );
+    assert_eq!(
+        "../libnya".parse::<Source>().ok(),
+        Some(Source::Local(PathBuf::from("../libnya")))
+    );
+}
+
+#[test]
+fn test_source_libraryname() {
+    assert_eq!(
+        "https://github.com/Arukana/libnya.git".parse::<Source>()
+                                                 .and_then(|source| source.get_libraryname())
+                                                 .ok(),
+        Some("arukana@libnya".to_string())
+    );
+    assert_eq!(
+        "./LibNya".parse::<Source>()
+                  .and_then(|source| source.get_libraryname())
+                  .ok(),
+        Some("local@libnya".to_string())
+    );
+}
+
+#[test]
+fn test_source_rebase() {
+    assert_eq!(
+        Source::Local(PathBuf::from("../libnya"))
+            .rebase(Path::new("/home/neko/libmeow")),
+        Source::Local(PathBuf::from("/home/neko/libmeow/../libnya"))
+    );
+    assert_eq!(
+        Source::Local(PathBuf::from("/home/neko/libnya"))
+            .rebase(Path::new("/home/neko/libmeow")),
+        Source::Local(PathBuf::from("/home/neko/libnya"))
+    );
+}
+
+#[test]
+fn test_source_display() {
+    assert_eq!(
+        Source::Git("https://github.com/Arukana/libnya.git".to_string(),
+                    Revision::Tag("v1.2.0".to_string())).to_string(),
+        "https://github.com/Arukana/libnya.git#tag=v1.2.0".to_string()
+    );
+    assert_eq!(
+        Source::Local(PathBuf::from("/home/neko/libnya")).to_string(),
+        "file:///home/neko/libnya".to_string()
+    );
+}
@@ -0,0 +1,64 @@
+extern crate neko;
+
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use neko::prelude::*;
+use neko::dynamic::swap;
+
+fn read(path: &Path) -> String {
+    let mut text: String = String::new();
+
+    fs::File::open(path).unwrap().read_to_string(&mut text).unwrap();
+    text
+}
+
+fn write(path: &Path, text: &str) {
+    fs::File::create(path).unwrap().write_all(text.as_bytes()).unwrap();
+}
+
+#[test]
+fn test_swap_exchange() {
+    let path: PathBuf = env::temp_dir().join("neko-test-swap-exchange");
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    write(&path.join("nya"), "nya");
+    write(&path.join("meow"), "meow");
+    swap::exchange(&path.join("nya"), &path.join("meow")).unwrap();
+    assert_eq!(read(&path.join("nya")), "meow");
+    assert_eq!(read(&path.join("meow")), "nya");
+    assert!(path.join("nya").with_extension(swap::SPEC_SWAP_EXT).exists().not());
+
+    fs::remove_file(path.join("meow")).unwrap();
+    swap::exchange(&path.join("nya"), &path.join("meow")).unwrap();
+    assert!(path.join("nya").exists().not());
+    assert_eq!(read(&path.join("meow")), "meow");
+    swap::exchange(&path.join("nya"), &path.join("meow")).unwrap();
+    assert_eq!(read(&path.join("nya")), "meow");
+    assert!(path.join("meow").exists().not());
+}
+
+#[test]
+fn test_swap_recover() {
+    let path: PathBuf = env::temp_dir().join("neko-test-swap-recover");
+    let swapped: PathBuf = path.join("nya").with_extension(swap::SPEC_SWAP_EXT);
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    write(&swapped, "nya");
+    write(&path.join("meow"), "meow");
+    swap::recover(&path.join("nya"), &path.join("meow")).unwrap();
+    assert_eq!(read(&path.join("nya")), "nya");
+    assert!(swapped.exists().not());
+
+    fs::rename(path.join("nya"), &swapped).unwrap();
+    fs::rename(path.join("meow"), path.join("nya")).unwrap();
+    swap::exchange(&path.join("nya"), &path.join("meow")).unwrap();
+    assert_eq!(read(&path.join("nya")), "nya");
+    assert_eq!(read(&path.join("meow")), "meow");
+    assert!(swapped.exists().not());
+}
@@ -0,0 +1,95 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::trust::{self, Trust};
+
+#[test]
+fn test_trust_parse() {
+    let trust: Trust = "accounts = [\"github.com/arukana\", \"localhost/adjivas\"]\n\
+                        hosts = [\"git.example.org\"]\n\
+                        dependencies = \"trust\"\n\
+                        signed = true\n\
+                        fingerprints = [\"d869 2123 c406 5dea\"]\n"
+        .parse::<Trust>().unwrap();
+
+    assert!(trust.is_trusted("arukana", Some("github.com")));
+    assert!(trust.is_trusted("Arukana", Some("github.com")));
+    assert!(!trust.is_trusted("arukana", Some("gitlab.com")));
+    assert!(!trust.is_trusted("arukana", None));
+    assert!(trust.is_trusted("adjivas", Some("git.example.org")));
+    assert!(!trust.is_trusted("adjivas", Some("github.com")));
+    assert!(trust.is_trusted("adjivas", None));
+    assert!(!trust.is_refusing());
+    assert!(trust.is_signed());
+    assert!(trust.is_fingerprint("D86921 23C4065DEA"));
+    assert!(!trust.is_fingerprint("5E0F3AB5249B39D2"));
+}
+
+#[test]
+fn test_trust_default() {
+    let trust: Trust = "".parse::<Trust>().unwrap();
+
+    assert!(!trust.is_trusted("arukana", Some("github.com")));
+    assert!(trust.is_refusing());
+    assert!(!trust.is_signed());
+    assert!(trust.get_fingerprints().is_empty());
+    assert!(Trust::default().is_refusing());
+}
+
+#[test]
+fn test_trust_unvalid() {
+    assert_eq!("dependencies = \"maybe\"\n".parse::<Trust>().err(),
+               Some(CompositerError::ParseTrust));
+    assert_eq!("accounts = \"arukana\"\n".parse::<Trust>().err(),
+               Some(CompositerError::ParseTrust));
+    assert_eq!("accounts = [\"arukana\"]\n".parse::<Trust>().err(),
+               Some(CompositerError::ParseTrust));
+}
+
+#[test]
+fn test_trust_round_trip() {
+    let mut trust: Trust = Trust::default();
+
+    trust.insert_account("github.com/arukana".to_string());
+    trust.insert_account("github.com/arukana".to_string());
+    trust.insert_host("github.com".to_string());
+    trust.insert_fingerprint("d869 2123");
+    assert_eq!(trust.get_accounts(), &["github.com/arukana".to_string()]);
+    assert_eq!(trust.get_fingerprints(), &["D8692123".to_string()]);
+    assert_eq!(trust.to_string().parse::<Trust>().ok(), Some(trust));
+}
+
+#[test]
+fn test_trust_host() {
+    assert_eq!(trust::get_host("https://github.com/Arukana/libnya.git"),
+               Some("github.com".to_string()));
+    assert_eq!(trust::get_host("git@GitHub.com:Arukana/libnya.git"),
+               Some("github.com".to_string()));
+    assert_eq!(trust::get_host("ssh://git@github.com:22/Arukana/libnya.git"),
+               Some("github.com".to_string()));
+    assert_eq!(trust::get_host("file:///home/neko/libnya"), None);
+    assert_eq!(trust::get_host("/home/neko/libnya"), None);
+}
+
+#[test]
+fn test_trust_account() {
+    assert_eq!(trust::get_account("Arukana", Some("github.com")), "github.com/arukana");
+    assert_eq!(trust::get_account("arukana", None), "localhost/arukana");
+    assert!(trust::is_account("github.com/arukana"));
+    assert!(!trust::is_account("arukana"));
+    assert!(!trust::is_account("github.com/"));
+    assert!(!trust::is_account("github.com/arukana/libnya"));
+}
+
+#[test]
+fn test_trust_valid_fingerprints() {
+    let status: &str = "[GNUPG:] NEWSIG\n\
+                        [GNUPG:] GOODSIG 249B39D24F25E3B6 Neko <neko@example.org>\n\
+                        [GNUPG:] VALIDSIG 5E0F3AB5249B39D24F25E3B6D8692123C4065DEA 2017-03-01 \
+                        1488326400 0 4 0 1 8 00 D8692123C4065DEA5E0F3AB5249B39D24F25E3B6\n";
+
+    assert_eq!(trust::get_valid_fingerprints(status),
+               vec!["5E0F3AB5249B39D24F25E3B6D8692123C4065DEA".to_string(),
+                    "D8692123C4065DEA5E0F3AB5249B39D24F25E3B6".to_string()]);
+    assert!(trust::get_valid_fingerprints("[GNUPG:] BADSIG 249B39D24F25E3B6\n").is_empty());
+}
@@ -0,0 +1,50 @@
+extern crate neko;
+
+use neko::prelude::*;
+use neko::dynamic::version::Requirements;
+
+#[test]
+fn test_requirements_select() {
+    let mut requirements: Requirements = Requirements::default();
+
+    assert_eq!(requirements.insert("arukana@libnya".to_string(),
+                                   "arukana@libmeow".to_string(),
+                                   "^0.3").err(), None);
+    assert_eq!(requirements.insert("arukana@libnya".to_string(),
+                                   "arukana@libpurr".to_string(),
+                                   ">=0.3.1").err(), None);
+    assert_eq!(
+        requirements.select("arukana@libnya",
+                            vec!["v0.2.9", "v0.3.0", "v0.3.2", "0.3.4", "v0.4.0", "nightly"])
+                    .ok(),
+        Some("0.3.4".to_string())
+    );
+}
+
+#[test]
+fn test_requirements_conflict() {
+    let mut requirements: Requirements = Requirements::default();
+
+    assert_eq!(requirements.insert("arukana@libnya".to_string(),
+                                   "arukana@libmeow".to_string(),
+                                   "^0.3").err(), None);
+    assert_eq!(requirements.insert("arukana@libnya".to_string(),
+                                   "arukana@libpurr".to_string(),
+                                   "^0.4").err(), None);
+    assert_eq!(
+        requirements.select("arukana@libnya", vec!["v0.3.2", "v0.4.0"]).err(),
+        Some(CompositerError::VersionConflict("arukana@libnya".to_string(), Vec::new()))
+    );
+}
+
+#[test]
+fn test_requirements_unvalid() {
+    let mut requirements: Requirements = Requirements::default();
+
+    assert_eq!(
+        requirements.insert("arukana@libnya".to_string(),
+                            "arukana@libmeow".to_string(),
+                            "nya").err(),
+        Some(CompositerError::ParseVersion("nya".to_string()))
+    );
+}
@@ -0,0 +1,36 @@
+extern crate neko;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use neko::prelude::*;
+use neko::dynamic::watch::Watch;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_watch_changes() {
+    let path: PathBuf = env::temp_dir().join("neko-test-watch");
+
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+
+    let watch: Watch = Watch::new(path.clone()).unwrap();
+
+    assert!(watch.changes().is_empty());
+    fs::File::create(path.join("arukana@libnya.so")).unwrap()
+                                                     .write_all(b"nya").unwrap();
+    fs::File::create(path.join(".arukana@libmeow.so")).unwrap()
+                                                      .write_all(b"meow").unwrap();
+    fs::rename(path.join(".arukana@libmeow.so"),
+               path.join("arukana@libmeow.so")).unwrap();
+    assert_eq!(
+        watch.changes(),
+        vec![path.join("arukana@libnya.so"),
+             path.join(".arukana@libmeow.so"),
+             path.join("arukana@libmeow.so")]
+    );
+    assert!(watch.changes().is_empty());
+    let _ = fs::remove_dir_all(&path);
+}